@@ -0,0 +1,333 @@
+//! Maintenance tool for the debloat lists.
+//!
+//! Usage: `uad-lists lint [--json] [path]` (defaults to `resources/assets/uad_lists.json`)
+//!
+//! This binary doesn't depend on the GUI: the entries are deserialized in raw structs so
+//! that invalid values can be reported instead of failing the whole parsing.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::fs;
+use std::process::ExitCode;
+
+const DEFAULT_PATH: &str = "resources/assets/uad_lists.json";
+const LISTS: [&str; 6] = ["Aosp", "Carrier", "Google", "Misc", "Oem", "Pending"];
+const REMOVALS: [&str; 4] = ["Recommended", "Advanced", "Expert", "Unsafe"];
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RawPackage {
+    id: Option<Value>,
+    list: Option<Value>,
+    description: Option<Value>,
+    descriptions: Option<Value>, // optional translations
+    dependencies: Option<Value>,
+    needed_by: Option<Value>,
+    removal: Option<Value>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct Issue {
+    severity: Severity,
+    check: &'static str,
+    index: usize, // position of the entry in the list
+    id: String,
+    message: String,
+}
+
+// `com.foo.bar`: letters, digits and underscores, segments starting with a letter
+fn is_valid_package_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('.').all(|segment| {
+            segment.starts_with(|c: char| c.is_ascii_alphabetic())
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+fn string_array(value: Option<&Value>) -> Option<Vec<&str>> {
+    value?.as_array()?.iter().map(Value::as_str).collect()
+}
+
+fn lint(packages: &[RawPackage]) -> Vec<Issue> {
+    let mut issues = vec![];
+    let mut push = |severity, check, index, id: &str, message: String| {
+        issues.push(Issue {
+            severity,
+            check,
+            index,
+            id: id.to_string(),
+            message,
+        });
+    };
+
+    let ids: Vec<&str> = packages
+        .iter()
+        .map(|p| p.id.as_ref().and_then(Value::as_str).unwrap_or_default())
+        .collect();
+    let known: HashSet<&str> = ids.iter().copied().collect();
+    let mut seen: HashMap<&str, usize> = HashMap::new(); // index of the first definition
+    let mut dependencies: HashMap<&str, Vec<&str>> = HashMap::new();
+    let mut needed_by: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for (i, (p, &id)) in packages.iter().zip(&ids).enumerate() {
+        if id.is_empty() {
+            push(
+                Severity::Error,
+                "missing-id",
+                i,
+                id,
+                "`id` is missing or is not a string".to_string(),
+            );
+            continue;
+        }
+        let duplicate = match seen.entry(id) {
+            Entry::Occupied(first) => {
+                push(
+                    Severity::Error,
+                    "duplicate-id",
+                    i,
+                    id,
+                    format!("already defined at index {}", first.get()),
+                );
+                true
+            }
+            Entry::Vacant(e) => {
+                e.insert(i);
+                false
+            }
+        };
+        if !is_valid_package_name(id) {
+            push(
+                Severity::Error,
+                "package-name",
+                i,
+                id,
+                "invalid package name".to_string(),
+            );
+        }
+
+        for (field, value, allowed) in [
+            ("list", &p.list, &LISTS[..]),
+            ("removal", &p.removal, &REMOVALS[..]),
+        ] {
+            match value.as_ref().and_then(Value::as_str) {
+                Some(v) if allowed.contains(&v) => {}
+                Some(v) => push(
+                    Severity::Error,
+                    "unknown-value",
+                    i,
+                    id,
+                    format!("unknown `{field}` value `{v}` (expected one of {allowed:?})"),
+                ),
+                None => push(
+                    Severity::Error,
+                    "schema",
+                    i,
+                    id,
+                    format!("`{field}` is missing or is not a string"),
+                ),
+            }
+        }
+
+        match p.description.as_ref().map(Value::as_str) {
+            Some(Some(d)) if d.trim().is_empty() => push(
+                Severity::Warning,
+                "empty-description",
+                i,
+                id,
+                "empty description".to_string(),
+            ),
+            Some(Some(_)) => {}
+            _ => push(
+                Severity::Error,
+                "schema",
+                i,
+                id,
+                "`description` is missing or is not a string".to_string(),
+            ),
+        }
+
+        if let Some(descriptions) = &p.descriptions {
+            if !descriptions
+                .as_object()
+                .is_some_and(|d| d.values().all(Value::is_string))
+            {
+                push(
+                    Severity::Error,
+                    "schema",
+                    i,
+                    id,
+                    "`descriptions` is not a map of strings".to_string(),
+                );
+            }
+        }
+
+        for (field, value, links) in [
+            ("dependencies", &p.dependencies, &mut dependencies),
+            ("neededBy", &p.needed_by, &mut needed_by),
+        ] {
+            let Some(refs) = string_array(value.as_ref()) else {
+                push(
+                    Severity::Error,
+                    "schema",
+                    i,
+                    id,
+                    format!("`{field}` is missing or is not an array of strings"),
+                );
+                continue;
+            };
+            for r in &refs {
+                if !known.contains(r) {
+                    push(
+                        Severity::Error,
+                        "dangling-reference",
+                        i,
+                        id,
+                        format!("`{field}` references `{r}` which is not in the list"),
+                    );
+                }
+            }
+            if !duplicate {
+                links.insert(id, refs);
+            }
+        }
+    }
+
+    // `a` depends on `b` <=> `b` is needed by `a`
+    for (i, &id) in ids.iter().enumerate() {
+        if seen.get(id) != Some(&i) {
+            continue;
+        }
+        for &dep in dependencies.get(id).into_iter().flatten() {
+            let back = needed_by.get(dep).is_some_and(|n| n.contains(&id));
+            if known.contains(dep) && !back {
+                push(
+                    Severity::Warning,
+                    "asymmetric-dependency",
+                    i,
+                    id,
+                    format!("depends on `{dep}` but `{dep}` isn't needed by `{id}`"),
+                );
+            }
+        }
+        for &user in needed_by.get(id).into_iter().flatten() {
+            let back = dependencies.get(user).is_some_and(|d| d.contains(&id));
+            if known.contains(user) && !back {
+                push(
+                    Severity::Warning,
+                    "asymmetric-dependency",
+                    i,
+                    id,
+                    format!("is needed by `{user}` but `{user}` doesn't depend on `{id}`"),
+                );
+            }
+        }
+    }
+    issues
+}
+
+fn usage() -> ExitCode {
+    eprintln!("Usage: uad-lists lint [--json] [path]");
+    ExitCode::from(2)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) != Some("lint") {
+        return usage();
+    }
+    let json = args.iter().any(|a| a == "--json");
+    let path = match args[1..]
+        .iter()
+        .filter(|a| *a != "--json")
+        .collect::<Vec<_>>()[..]
+    {
+        [] => DEFAULT_PATH,
+        [path] => path.as_str(),
+        _ => return usage(),
+    };
+
+    let packages: Vec<RawPackage> = match fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| serde_json::from_str(&s).map_err(|e| e.to_string()))
+    {
+        Ok(packages) => packages,
+        Err(e) => {
+            eprintln!("{path}: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let issues = lint(&packages);
+    let nb_errors = issues
+        .iter()
+        .filter(|i| i.severity == Severity::Error)
+        .count();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&issues).expect("issues should be serializable")
+        );
+    } else {
+        for issue in &issues {
+            println!(
+                "{path}[{}] {:?} ({}) {}: {}",
+                issue.index, issue.severity, issue.check, issue.id, issue.message
+            );
+        }
+        println!(
+            "{} entries, {} error(s), {} warning(s)",
+            packages.len(),
+            nb_errors,
+            issues.len() - nb_errors
+        );
+    }
+
+    if nb_errors == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint() {
+        const DATA: &str = r#"[
+            {"id": "com.a", "list": "Oem", "description": "A", "dependencies": ["com.b"],
+             "neededBy": [], "labels": [], "removal": "Recommended"},
+            {"id": "com.b", "list": "Oem", "description": "", "dependencies": [],
+             "neededBy": ["com.z"], "labels": [], "removal": "Advanced"},
+            {"id": "com.a", "list": "Foo", "description": "A", "dependencies": [],
+             "neededBy": [], "labels": [], "removal": "Recommended"},
+            {"id": "1com", "list": "Oem", "description": "C", "descriptions": {"fr": 1}, "dependencies": [],
+             "neededBy": [], "labels": [], "removal": "Nope"}
+        ]"#;
+        let packages: Vec<RawPackage> = serde_json::from_str(DATA).unwrap();
+        let checks: Vec<(usize, &str)> =
+            lint(&packages).iter().map(|i| (i.index, i.check)).collect();
+
+        assert!(checks.contains(&(1, "empty-description")));
+        assert!(checks.contains(&(1, "dangling-reference")));
+        assert!(checks.contains(&(2, "duplicate-id")));
+        assert!(checks.contains(&(2, "unknown-value")));
+        assert!(checks.contains(&(3, "package-name")));
+        assert!(checks.contains(&(3, "unknown-value")));
+        assert!(checks.contains(&(3, "schema")));
+        assert!(checks.contains(&(0, "asymmetric-dependency")));
+        assert_eq!(checks.len(), 8);
+    }
+}
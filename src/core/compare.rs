@@ -0,0 +1,58 @@
+use crate::core::uad_lists::PackageState;
+use std::collections::HashMap;
+
+/// States of the packages of a source (user, device or backup) by package name
+pub type States<'a> = HashMap<&'a str, PackageState>;
+
+/// The package is not in the same state in all the sources, or is missing from some of them
+pub fn differs(name: &str, sources: &[States]) -> bool {
+    let mut states = sources.iter().map(|s| s.get(name));
+    states
+        .next()
+        .is_some_and(|first| states.any(|s| s != first))
+}
+
+/// (package name, state in `reference`) of the packages of `target` in another state.
+///
+/// Packages missing from `target` are left out: UAD can't install them.
+pub fn alignment<'a>(reference: &States<'a>, target: &States) -> Vec<(&'a str, PackageState)> {
+    let mut changes: Vec<(&str, PackageState)> = reference
+        .iter()
+        .filter(|(name, state)| target.get(*name).is_some_and(|s| s != *state))
+        .map(|(name, state)| (*name, *state))
+        .collect();
+    changes.sort_unstable_by_key(|c| c.0);
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alignment() {
+        let owner = States::from([
+            ("com.a", PackageState::Uninstalled),
+            ("com.b", PackageState::Enabled),
+            ("com.c", PackageState::Disabled),
+        ]);
+        let work = States::from([
+            ("com.a", PackageState::Enabled),
+            ("com.b", PackageState::Enabled),
+        ]);
+        let sources = [owner.clone(), work.clone()];
+        assert!(differs("com.a", &sources));
+        assert!(!differs("com.b", &sources));
+        assert!(differs("com.c", &sources));
+        assert!(!differs("com.c", &sources[..1]));
+
+        assert_eq!(
+            alignment(&owner, &work),
+            vec![("com.a", PackageState::Uninstalled)]
+        );
+        assert_eq!(
+            alignment(&work, &owner),
+            vec![("com.a", PackageState::Enabled)]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::core::sync::{get_android_sdk, User};
+use crate::core::uad_lists::{ListSource, PackageState};
 use crate::core::utils::DisplayablePath;
 use crate::gui::views::settings::Settings;
 use crate::CONFIG_DIR;
@@ -18,6 +19,12 @@ pub struct Config {
 pub struct GeneralSettings {
     pub theme: String,
     pub expert_mode: bool,
+    #[serde(default)]
+    pub background_list_refresh: bool,
+    #[serde(default)]
+    pub language: String, // ISO 639-1 code, English if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub list_sources: Vec<ListSource>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -33,7 +40,13 @@ pub struct BackupSettings {
 pub struct DeviceSettings {
     pub device_id: String,
     pub disable_mode: bool,
+    #[serde(default)]
+    pub suspend_mode: bool,
     pub multi_user_mode: bool,
+    #[serde(default)]
+    pub trial_mode: bool,
+    #[serde(default)]
+    pub root_mode: bool, // run package state changes through `su`
     #[serde(skip)]
     pub backup: BackupSettings,
 }
@@ -44,11 +57,27 @@ impl Default for DeviceSettings {
             device_id: String::new(),
             multi_user_mode: get_android_sdk() > 21,
             disable_mode: false,
+            suspend_mode: false,
+            trial_mode: false,
+            root_mode: false,
             backup: BackupSettings::default(),
         }
     }
 }
 
+impl DeviceSettings {
+    /// State in which packages end up when the user removes them
+    pub const fn removal_state(&self) -> PackageState {
+        if self.suspend_mode {
+            PackageState::Suspended
+        } else if self.disable_mode {
+            PackageState::Disabled
+        } else {
+            PackageState::Uninstalled
+        }
+    }
+}
+
 #[dynamic]
 static CONFIG_FILE: PathBuf = CONFIG_DIR.join("config.toml");
 
@@ -0,0 +1,175 @@
+use crate::core::sync::{adb_shell_command, hashmap_packages_dump};
+use crate::core::uad_lists::{Removal, UadList};
+use crate::CACHE_DIR;
+use serde::Serialize;
+use static_init::dynamic;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[dynamic]
+pub static CONTRIBUTIONS_DIR: PathBuf = CACHE_DIR.join("contributions");
+
+/// Unlisted package described by the user
+#[derive(Default, Debug, Clone)]
+pub struct Contribution {
+    pub id: String,
+    pub apk_path: String,
+    pub version: String,
+    pub installer: String,
+    pub description: String,
+    pub list: UadList,
+    pub removal: Removal,
+}
+
+impl Contribution {
+    // There is no way to get the label of an app with adb only (it needs `aapt`) but the
+    // name of its APK is usually close enough to show it
+    pub fn apk_name(&self) -> &str {
+        self.apk_path
+            .rsplit('/')
+            .next()
+            .and_then(|f| f.strip_suffix(".apk"))
+            .unwrap_or_default()
+    }
+
+    // Where the package was found, to help the maintainers review the entry
+    fn device_details(&self) -> String {
+        [
+            ("APK", &self.apk_path),
+            ("Version", &self.version),
+            ("Installer", &self.installer),
+        ]
+        .iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(key, value)| format!("{key}: {value}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+}
+
+// Entry of `uad_lists.json`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ListEntry<'a> {
+    id: &'a str,
+    list: UadList,
+    description: String,
+    dependencies: Vec<String>,
+    needed_by: Vec<String>,
+    labels: Vec<String>,
+    removal: Removal,
+}
+
+pub async fn collect_unlisted_packages(packages: Vec<String>) -> Vec<Contribution> {
+    let mut packages_dump = hashmap_packages_dump();
+
+    // `package:/system/app/Foo/Foo.apk=com.foo`
+    let apk_paths: HashMap<String, String> = adb_shell_command(true, "pm list packages -f")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| l.strip_prefix("package:")?.rsplit_once('='))
+        .map(|(path, name)| (name.to_string(), path.to_string()))
+        .collect();
+
+    packages
+        .into_iter()
+        .map(|id| {
+            let dump = packages_dump.remove(&id).unwrap_or_default();
+            Contribution {
+                apk_path: apk_paths.get(&id).cloned().unwrap_or(dump.code_path),
+                version: dump.version_name,
+                installer: if dump.installer == "null" {
+                    String::new()
+                } else {
+                    dump.installer
+                },
+                list: UadList::Pending,
+                removal: Removal::Expert,
+                id,
+                ..Contribution::default()
+            }
+        })
+        .collect()
+}
+
+/// Builds a JSON fragment following the schema of `uad_lists.json`.
+///
+/// Packages without a description are skipped. The APK path, version and installer of the
+/// package end the description.
+pub fn contributions_to_json(contributions: &[Contribution]) -> String {
+    let entries: Vec<ListEntry> = contributions
+        .iter()
+        .filter(|c| !c.description.trim().is_empty())
+        .map(|c| {
+            let details = c.device_details();
+            ListEntry {
+                id: &c.id,
+                list: c.list,
+                description: if details.is_empty() {
+                    c.description.trim().to_string()
+                } else {
+                    format!("{}\n\n{details}", c.description.trim())
+                },
+                dependencies: vec![],
+                needed_by: vec![],
+                labels: vec![],
+                removal: c.removal,
+            }
+        })
+        .collect();
+    serde_json::to_string_pretty(&entries).expect("entries should be serializable")
+}
+
+pub fn export_contributions(
+    contributions: &[Contribution],
+    device_id: &str,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(&*CONTRIBUTIONS_DIR).map_err(|e| e.to_string())?;
+    let path = CONTRIBUTIONS_DIR.join(format!(
+        "{}_{}.json",
+        device_id,
+        chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")
+    ));
+    fs::write(&path, contributions_to_json(contributions)).map_err(|e| e.to_string())?;
+    info!("[CONTRIBUTE] Contributions exported to {}", path.display());
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::uad_lists::parse_list;
+
+    #[test]
+    fn test_contributions_to_json() {
+        let contributions = [
+            Contribution {
+                id: "com.foo".to_string(),
+                apk_path: "/system/app/Foo/Foo.apk".to_string(),
+                version: "1.2".to_string(),
+                description: "Foo tracker\n".to_string(),
+                list: UadList::Oem,
+                removal: Removal::Recommended,
+                ..Contribution::default()
+            },
+            Contribution {
+                id: "com.bar".to_string(),
+                ..Contribution::default()
+            },
+        ];
+        assert_eq!(contributions[0].apk_name(), "Foo");
+
+        let json = contributions_to_json(&contributions);
+        assert!(!json.contains("apkPath"));
+
+        let list = parse_list(&json).unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list[0].removal, Removal::Recommended);
+        assert_eq!(
+            list[0].description,
+            "Foo tracker\n\nAPK: /system/app/Foo/Foo.apk\nVersion: 1.2"
+        );
+        assert!(list[0].labels().is_empty());
+    }
+}
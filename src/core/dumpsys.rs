@@ -0,0 +1,426 @@
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ComponentKind {
+    Activity,
+    Service,
+    Receiver,
+    Provider,
+    Unknown,
+}
+
+impl std::fmt::Display for ComponentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Activity => "activity",
+                Self::Service => "service",
+                Self::Receiver => "receiver",
+                Self::Provider => "provider",
+                Self::Unknown => "unknown",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub name: String, // fully qualified class name
+    pub kind: ComponentKind,
+    pub enabled: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct UserDump {
+    pub suspended: bool,
+    pub disabled_components: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct PackageDump {
+    pub name: String,
+    pub code_path: String,
+    pub version_name: String,
+    pub installer: String,
+    pub updated_system_app: bool,
+    pub users: HashMap<u16, UserDump>,
+}
+
+impl PackageDump {
+    // `/system/priv-app/SamsungCloud` -> `SamsungCloud`: the name of the APK or of its
+    // directory, not the label of the app (it needs `aapt`, which devices don't have)
+    pub fn apk_name(&self) -> &str {
+        self.code_path
+            .rsplit('/')
+            .next()
+            .map(|f| f.strip_suffix(".apk").unwrap_or(f))
+            .unwrap_or_default()
+    }
+}
+
+// Sizes in bytes
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackageSize {
+    pub code: u64,
+    pub data: u64,
+    pub cache: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    None,
+    DisabledComponents(usize), // indentation of the section header
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+// `User 0: ceDataInode=...` -> Some(0)
+fn parse_user_line(line: &str) -> Option<u16> {
+    line.trim_start()
+        .strip_prefix("User ")?
+        .split_once(':')?
+        .0
+        .parse()
+        .ok()
+}
+
+/// Parses the `Packages:` section of `dumpsys package [packages|<package>]`.
+///
+/// The `Hidden system packages:` section (factory versions of updated system apps)
+/// is ignored.
+pub fn parse_packages_dump(dump: &str) -> HashMap<String, PackageDump> {
+    let mut packages: HashMap<String, PackageDump> = HashMap::new();
+    let mut current: Option<PackageDump> = None;
+    let mut user_id = 0;
+    let mut section = Section::None;
+
+    for line in dump.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Section::DisabledComponents(indent) = section {
+            if indentation(line) > indent {
+                if let Some(p) = current.as_mut() {
+                    p.users
+                        .entry(user_id)
+                        .or_default()
+                        .disabled_components
+                        .push(trimmed.to_string());
+                }
+                continue;
+            }
+            section = Section::None;
+        }
+
+        if trimmed == "Hidden system packages:" {
+            break;
+        }
+
+        if let Some(name) = trimmed
+            .strip_prefix("Package [")
+            .and_then(|s| s.split_once(']'))
+            .map(|(name, _)| name)
+        {
+            if let Some(p) = current.take() {
+                packages.insert(p.name.clone(), p);
+            }
+            current = Some(PackageDump {
+                name: name.to_string(),
+                ..PackageDump::default()
+            });
+        } else if let Some(id) = parse_user_line(line) {
+            user_id = id;
+            if let Some(p) = current.as_mut() {
+                p.users.entry(id).or_default().suspended = trimmed.contains(" suspended=true");
+            }
+        } else if trimmed == "disabledComponents:" {
+            section = Section::DisabledComponents(indentation(line));
+        } else if let Some(p) = current.as_mut() {
+            if let Some(path) = trimmed.strip_prefix("codePath=") {
+                p.code_path = path.to_string();
+            } else if let Some(version) = trimmed.strip_prefix("versionName=") {
+                p.version_name = version.to_string();
+            } else if let Some(installer) = trimmed.strip_prefix("installerPackageName=") {
+                p.installer = installer.to_string();
+            } else if trimmed.starts_with("flags=[") || trimmed.starts_with("pkgFlags=[") {
+                p.updated_system_app |= trimmed.contains(" UPDATED_SYSTEM_APP ");
+            }
+        }
+    }
+    if let Some(p) = current {
+        packages.insert(p.name.clone(), p);
+    }
+    packages
+}
+
+// `com.foo/.Bar` -> `com.foo.Bar` and `com.foo/com.baz.Bar` -> `com.baz.Bar`
+fn full_class_name(package: &str, short: &str) -> String {
+    short.strip_prefix('.').map_or_else(
+        || short.to_string(),
+        |relative| format!("{package}.{relative}"),
+    )
+}
+
+/// Lists the components of a package from the output of `dumpsys package <package>`.
+///
+/// Only components declaring an intent filter appear in the resolver tables so
+/// disabled components without any filter are added with an `Unknown` kind.
+pub fn parse_components(dump: &str, package: &str, user_id: u16) -> Vec<Component> {
+    let prefix = format!("{package}/");
+    let mut kind = None;
+    let mut found: BTreeSet<(ComponentKind, String)> = BTreeSet::new();
+
+    for line in dump.lines() {
+        if indentation(line) == 0 {
+            kind = match line.trim_end() {
+                "Activity Resolver Table:" => Some(ComponentKind::Activity),
+                "Receiver Resolver Table:" => Some(ComponentKind::Receiver),
+                "Service Resolver Table:" => Some(ComponentKind::Service),
+                "Provider Resolver Table:" | "Registered ContentProviders:" => {
+                    Some(ComponentKind::Provider)
+                }
+                _ => None,
+            };
+            continue;
+        }
+        if let Some(kind) = kind {
+            for token in line.split_whitespace() {
+                if let Some(short) = token.trim_end_matches(':').strip_prefix(&prefix) {
+                    found.insert((kind, full_class_name(package, short)));
+                }
+            }
+        }
+    }
+
+    let disabled = parse_packages_dump(dump)
+        .remove(package)
+        .and_then(|mut p| p.users.remove(&user_id))
+        .unwrap_or_default()
+        .disabled_components;
+
+    let mut components: Vec<Component> = found
+        .into_iter()
+        .map(|(kind, name)| Component {
+            enabled: !disabled.contains(&name),
+            name,
+            kind,
+        })
+        .collect();
+
+    for name in disabled {
+        if !components.iter().any(|c| c.name == name) {
+            components.push(Component {
+                name,
+                kind: ComponentKind::Unknown,
+                enabled: false,
+            });
+        }
+    }
+    components
+}
+
+/// Parses the per-package sizes of `dumpsys diskstats` (Android 8.0+).
+///
+/// These sizes are computed by a daily job of the system so they don't reflect
+/// changes made since then.
+pub fn parse_diskstats(dump: &str) -> HashMap<String, PackageSize> {
+    fn array<T: serde::de::DeserializeOwned>(dump: &str, prefix: &str) -> Vec<T> {
+        dump.lines()
+            .find_map(|l| l.strip_prefix(prefix))
+            .and_then(|a| serde_json::from_str(a.trim()).ok())
+            .unwrap_or_default()
+    }
+
+    let names: Vec<String> = array(dump, "Package Names:");
+    let code: Vec<u64> = array(dump, "App Sizes:");
+    let data: Vec<u64> = array(dump, "App Data Sizes:");
+    let cache: Vec<u64> = array(dump, "Cache Sizes:");
+
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let size = PackageSize {
+                code: code.get(i).copied().unwrap_or_default(),
+                data: data.get(i).copied().unwrap_or_default(),
+                cache: cache.get(i).copied().unwrap_or_default(),
+            };
+            (name, size)
+        })
+        .collect()
+}
+
+// Android user as listed by `pm list users` and `dumpsys user`
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct UserInfoDump {
+    pub id: u16,
+    pub name: String,
+    pub flags: u32,
+    pub running: bool,
+    pub user_type: String, // e.g. `profile.MANAGED` (Android 11+)
+}
+
+// `UserInfo{10:Work profile:1030} running` -> id 10, flags 0x1030, running
+fn parse_user_info(line: &str) -> Option<UserInfoDump> {
+    let start = line.find("UserInfo{")? + "UserInfo{".len();
+    let end = start + line[start..].find('}')?;
+    let (id, rest) = line[start..end].split_once(':')?;
+    let (name, flags) = rest.rsplit_once(':')?;
+    Some(UserInfoDump {
+        id: id.parse().ok()?,
+        name: name.to_string(),
+        flags: u32::from_str_radix(flags, 16).ok()?,
+        running: line[end..].contains("running"),
+        user_type: String::new(),
+    })
+}
+
+/// Parses `pm list users`, completed by `dumpsys user` (empty if unavailable)
+pub fn parse_users(pm_list_users: &str, dumpsys_user: &str) -> Vec<UserInfoDump> {
+    let mut users: Vec<UserInfoDump> = pm_list_users.lines().filter_map(parse_user_info).collect();
+
+    let mut current = None;
+    for line in dumpsys_user.lines() {
+        if let Some(info) = parse_user_info(line) {
+            current = users.iter().position(|u| u.id == info.id);
+            continue;
+        }
+        // End of the `Users:` section
+        if indentation(line) == 0 {
+            current = None;
+        }
+        let Some(user) = current.map(|i| &mut users[i]) else {
+            continue;
+        };
+        let line = line.trim();
+        if let Some(user_type) = line.strip_prefix("Type: android.os.usertype.") {
+            user.user_type = user_type.to_string();
+        } else if let Some(state) = line.strip_prefix("State: ") {
+            user.running = state.starts_with("RUNNING");
+        }
+    }
+    users
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = "\
+Activity Resolver Table:
+  Non-Data Actions:
+      android.intent.action.MAIN:
+        5b0e7b4 com.foo/.MainActivity filter 2a5c6dd
+Receiver Resolver Table:
+  Non-Data Actions:
+      android.intent.action.BOOT_COMPLETED:
+        6d1a0f1 com.foo/com.bar.BootReceiver filter 1e2f3a4
+Packages:
+  Package [com.foo] (4c2a1b2):
+    userId=10123
+    codePath=/data/app/com.foo-1
+    versionName=2.1.0
+    installerPackageName=com.android.vending
+    flags=[ SYSTEM HAS_CODE UPDATED_SYSTEM_APP ]
+    User 0: ceDataInode=2359 installed=true hidden=false suspended=true
+      disabledComponents:
+        com.bar.BootReceiver
+        com.foo.Tracker
+    User 10: ceDataInode=0 installed=true hidden=false suspended=false
+
+Hidden system packages:
+  Package [com.foo] (1a2b3c4):
+    codePath=/system/app/Foo
+    User 0: ceDataInode=0 installed=true hidden=false suspended=false
+      disabledComponents:
+        com.foo.Hidden
+";
+
+    #[test]
+    fn test_parse_components() {
+        let components = parse_components(DUMP, "com.foo", 0);
+        assert_eq!(components.len(), 3);
+        assert!(components.contains(&Component {
+            name: "com.foo.MainActivity".to_string(),
+            kind: ComponentKind::Activity,
+            enabled: true,
+        }));
+        assert!(components.contains(&Component {
+            name: "com.bar.BootReceiver".to_string(),
+            kind: ComponentKind::Receiver,
+            enabled: false,
+        }));
+        assert!(components.contains(&Component {
+            name: "com.foo.Tracker".to_string(),
+            kind: ComponentKind::Unknown,
+            enabled: false,
+        }));
+
+        let packages = parse_packages_dump(DUMP);
+        assert!(packages["com.foo"].updated_system_app);
+        assert_eq!(packages["com.foo"].code_path, "/data/app/com.foo-1");
+        assert_eq!(packages["com.foo"].apk_name(), "com.foo-1");
+        assert_eq!(packages["com.foo"].version_name, "2.1.0");
+        assert_eq!(packages["com.foo"].installer, "com.android.vending");
+        assert!(packages["com.foo"].users[&0].suspended);
+        assert!(!packages["com.foo"].users[&10].suspended);
+
+        let user_10 = parse_components(DUMP, "com.foo", 10);
+        assert!(user_10.iter().all(|c| c.enabled));
+    }
+
+    #[test]
+    fn test_parse_diskstats() {
+        let dump = "\
+Latency: 2ms [512B Data Write]
+App Size: 1500
+Package Names: [\"com.foo\",\"com.bar\"]
+App Sizes: [1000,500]
+App Data Sizes: [200,100]
+Cache Sizes: [20,10]
+";
+        let sizes = parse_diskstats(dump);
+        assert_eq!(
+            sizes["com.bar"],
+            PackageSize {
+                code: 500,
+                data: 100,
+                cache: 10
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_users() {
+        let pm = "Users:\n\
+            \tUserInfo{0:Owner:c13} running\n\
+            \tUserInfo{10:Work profile:1030} running\n\
+            \tUserInfo{11:Guest:414}\n";
+        let dumpsys = "Users:\n\
+            \x20 UserInfo{0:Owner:c13} serialNo=0 isPrimary=true\n\
+            \x20   Type: android.os.usertype.full.SYSTEM\n\
+            \x20   State: RUNNING_UNLOCKED\n\
+            \x20 UserInfo{10:Work profile:1030} serialNo=10 isPrimary=false\n\
+            \x20   Type: android.os.usertype.profile.MANAGED\n\
+            \x20   State: SHUTDOWN\n\
+            Device owner id:-10000\n\
+            \x20 State: RUNNING_LOCKED\n";
+        let users = parse_users(pm, dumpsys);
+        assert_eq!(users.len(), 3);
+        assert_eq!(users[0].user_type, "full.SYSTEM");
+        assert_eq!(users[1].name, "Work profile");
+        assert_eq!(users[1].flags, 0x1030);
+        assert_eq!(users[1].user_type, "profile.MANAGED");
+        assert!(!users[1].running);
+        assert_eq!(users[2].user_type, "");
+        assert!(!users[2].running);
+        assert_eq!(parse_users(pm, "").len(), 3);
+        assert!(parse_users(pm, "")[1].running);
+    }
+}
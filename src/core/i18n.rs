@@ -0,0 +1,101 @@
+use static_init::dynamic;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Language {
+    pub code: &'static str, // ISO 639-1
+    pub name: &'static str,
+}
+
+impl Language {
+    pub const ALL: [Self; 2] = [
+        Self {
+            code: "en",
+            name: "English",
+        },
+        Self {
+            code: "fr",
+            name: "Français",
+        },
+    ];
+
+    // Unknown codes (and the empty code of old config files) fall back to English
+    pub fn from_code(code: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|l| l.code == code)
+            .unwrap_or(Self::ALL[0])
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+// Catalogs map the English strings to their translation.
+// Missing entries are displayed in English.
+const CATALOGS: [(&str, &str); 1] = [("fr", include_str!("../../resources/i18n/fr.toml"))];
+
+struct Translations {
+    language: Language,
+    catalog: HashMap<String, String>,
+}
+
+#[dynamic]
+static TRANSLATIONS: RwLock<Translations> = RwLock::new(Translations {
+    language: Language::ALL[0],
+    catalog: HashMap::new(),
+});
+
+fn load_catalog(language: Language) -> HashMap<String, String> {
+    CATALOGS
+        .iter()
+        .find(|(code, _)| *code == language.code)
+        .map(|(_, catalog)| {
+            toml::from_str(catalog).unwrap_or_else(|e| {
+                error!("Invalid `{}` translation catalog: {}", language.code, e);
+                HashMap::new()
+            })
+        })
+        .unwrap_or_default()
+}
+
+pub fn set_language(code: &str) {
+    let language = Language::from_code(code);
+    let catalog = load_catalog(language);
+    if let Ok(mut translations) = TRANSLATIONS.write() {
+        *translations = Translations { language, catalog };
+    }
+}
+
+pub fn current_language() -> Language {
+    TRANSLATIONS
+        .read()
+        .map_or(Language::ALL[0], |translations| translations.language)
+}
+
+/// Translates an English string into the selected language
+pub fn tr(text: &str) -> String {
+    TRANSLATIONS
+        .read()
+        .ok()
+        .and_then(|translations| translations.catalog.get(text).cloned())
+        .unwrap_or_else(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogs() {
+        for language in Language::ALL.into_iter().skip(1) {
+            let catalog = load_catalog(language);
+            assert!(!catalog.is_empty(), "empty `{}` catalog", language.code);
+            assert!(catalog.values().all(|t| !t.trim().is_empty()));
+        }
+    }
+}
@@ -1,7 +1,17 @@
+pub mod compare;
 pub mod config;
+pub mod contribute;
+pub mod dumpsys;
+pub mod i18n;
+pub mod queue;
+pub mod recommend;
+pub mod root;
+pub mod safety;
 pub mod save;
+pub mod search;
 pub mod sync;
 pub mod theme;
 pub mod uad_lists;
 pub mod update;
+pub mod users;
 pub mod utils;
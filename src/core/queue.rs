@@ -0,0 +1,195 @@
+use crate::core::sync::{perform_adb_commands, AdbError, CommandType};
+use std::collections::VecDeque;
+use std::env;
+
+// Old devices drop adb commands when too many of them run at the same time
+const MAX_RUNNING: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    PackageState,
+    Restore,
+}
+
+/// adb commands of one package, run in order.
+///
+/// The first command changes the state of the package. The next ones (e.g. `pm clear`
+/// after `pm disable-user`) are only run if it succeeded.
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub kind: JobKind,
+    pub commands: Vec<String>,
+    pub command_type: CommandType,
+    device_id: String,
+}
+
+impl Job {
+    pub const fn new(kind: JobKind, commands: Vec<String>, command_type: CommandType) -> Self {
+        Self {
+            kind,
+            commands,
+            command_type,
+            device_id: String::new(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub cancelled: usize,
+}
+
+/// Jobs of the selected device, started in order and a few at a time.
+#[derive(Default, Debug, Clone)]
+pub struct Queue {
+    device_id: String,
+    pending: VecDeque<Job>,
+    running: usize,
+    total: usize, // nb of jobs since the queue was last cleared
+    pub paused: bool,
+    pub summary: Summary,
+}
+
+impl Queue {
+    /// Binds the queue to a device. Pending jobs of the previous device are cancelled and
+    /// returned.
+    pub fn set_device(&mut self, device_id: &str) -> Vec<Job> {
+        if self.device_id == device_id {
+            return vec![];
+        }
+        self.device_id = device_id.to_string();
+        self.cancel()
+    }
+
+    pub fn push(&mut self, jobs: impl IntoIterator<Item = Job>) {
+        // A new run starts with a new summary
+        if self.is_idle() {
+            self.clear();
+        }
+        for mut job in jobs {
+            job.device_id.clone_from(&self.device_id);
+            self.total += 1;
+            self.pending.push_back(job);
+        }
+    }
+
+    /// Jobs to start now
+    pub fn next_jobs(&mut self) -> Vec<Job> {
+        if self.paused {
+            return vec![];
+        }
+        let nb = MAX_RUNNING
+            .saturating_sub(self.running)
+            .min(self.pending.len());
+        self.running += nb;
+        self.pending.drain(..nb).collect()
+    }
+
+    pub fn job_done(&mut self, success: bool) {
+        // Jobs started before the last `clear`
+        if self.running == 0 {
+            return;
+        }
+        self.running -= 1;
+        if success {
+            self.summary.succeeded += 1;
+        } else {
+            self.summary.failed += 1;
+        }
+    }
+
+    /// Drops the jobs not started yet and returns them. Running jobs can't be stopped.
+    pub fn cancel(&mut self) -> Vec<Job> {
+        self.paused = false;
+        let jobs: Vec<Job> = self.pending.drain(..).collect();
+        self.summary.cancelled += jobs.len();
+        jobs
+    }
+
+    /// Forgets the summary of the last run
+    pub fn clear(&mut self) {
+        if self.is_idle() {
+            self.total = 0;
+            self.summary = Summary::default();
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.running == 0 && self.pending.is_empty()
+    }
+
+    /// (nb of finished jobs, nb of jobs) since the queue was last cleared
+    pub const fn progress(&self) -> (usize, usize) {
+        (
+            self.summary.succeeded + self.summary.failed + self.summary.cancelled,
+            self.total,
+        )
+    }
+}
+
+/// Runs the commands of a job. The result is the one of its first command.
+pub async fn run_job(job: Job) -> Result<CommandType, AdbError> {
+    let mut result = Err(AdbError::Failed("No command to run".to_string()));
+    for (i, command) in job.commands.into_iter().enumerate() {
+        // The selected device changed since the job was queued
+        if env::var("ANDROID_SERIAL").unwrap_or_default() != job.device_id {
+            warn!("[QUEUE] Device changed, skipping: {}", command);
+            if i == 0 {
+                result = Err(AdbError::DeviceChanged);
+            }
+            break;
+        }
+        let res = perform_adb_commands(command, job.command_type.clone()).await;
+        if i == 0 {
+            let failed = res.is_err();
+            result = res;
+            if failed {
+                break;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue() {
+        let job = || Job::new(JobKind::PackageState, vec![], CommandType::Shell);
+        let mut queue = Queue::default();
+        queue.set_device("a");
+        queue.push((0..6).map(|_| job()));
+        assert_eq!(queue.next_jobs().len(), MAX_RUNNING);
+        assert!(queue.next_jobs().is_empty());
+
+        queue.job_done(true);
+        queue.paused = true;
+        assert!(queue.next_jobs().is_empty());
+        queue.paused = false;
+        assert_eq!(queue.next_jobs().len(), 1);
+
+        queue.job_done(false);
+        assert_eq!(queue.cancel().len(), 1);
+        assert_eq!(queue.progress(), (3, 6));
+        (0..3).for_each(|_| queue.job_done(true));
+        assert!(queue.is_idle());
+        assert_eq!(
+            queue.summary,
+            Summary {
+                succeeded: 4,
+                failed: 1,
+                cancelled: 1
+            }
+        );
+
+        // A new run resets the summary, another device cancels it
+        queue.push([job(), job()]);
+        assert_eq!(queue.progress(), (0, 2));
+        assert_eq!(queue.set_device("b").len(), 2);
+        assert!(queue.is_idle());
+    }
+}
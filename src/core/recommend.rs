@@ -0,0 +1,232 @@
+use crate::core::i18n::tr;
+use crate::core::sync::adb_shell_command;
+use crate::core::uad_lists::{PackageState, Removal, UadList};
+use crate::gui::widgets::package_row::PackageRow;
+use std::collections::HashMap;
+
+#[derive(Default, Debug, Clone)]
+pub struct DeviceProfile {
+    pub brand: String,
+    pub carriers: Vec<String>, // one per SIM
+    pub android_sdk: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct Recommendation {
+    pub index: usize, // index of the package in the user's package list
+    pub name: String,
+    pub reason: String,
+}
+
+// Package name segments used by the OEMs (`com.sec.android.app.*` is Samsung)
+const BRAND_ALIASES: [(&str, &[&str]); 12] = [
+    ("samsung", &["samsung", "sec"]),
+    ("xiaomi", &["xiaomi", "miui"]),
+    ("redmi", &["xiaomi", "miui"]),
+    ("poco", &["xiaomi", "miui"]),
+    ("huawei", &["huawei", "hihonor"]),
+    ("honor", &["huawei", "hihonor", "honor"]),
+    ("oneplus", &["oneplus", "oplus"]),
+    ("oppo", &["oppo", "coloros", "oplus", "heytap"]),
+    ("realme", &["realme", "oplus", "heytap"]),
+    ("motorola", &["motorola", "moto"]),
+    ("lge", &["lge"]),
+    ("sony", &["sony", "sonyericsson", "somc"]),
+];
+
+const CARRIER_ALIASES: [(&str, &[&str]); 4] = [
+    ("verizon", &["verizon", "vzw"]),
+    ("tmobile", &["tmobile", "tmo"]),
+    ("att", &["att"]),
+    ("sprint", &["sprint"]),
+];
+
+pub async fn get_device_profile(android_sdk: u8) -> DeviceProfile {
+    let getprop = |prop| adb_shell_command(true, &format!("getprop {prop}")).unwrap_or_default();
+    DeviceProfile {
+        brand: getprop("ro.product.brand").trim().to_lowercase(),
+        // `Orange F,Free` on dual SIM devices
+        carriers: getprop("gsm.sim.operator.alpha")
+            .split(',')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(ToString::to_string)
+            .collect(),
+        android_sdk,
+    }
+}
+
+// `T-Mobile US` -> `tmobile`
+fn carrier_token(carrier: &str) -> String {
+    carrier
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn aliases<'a>(table: &[(&str, &'a [&'a str])], key: &'a str) -> Vec<&'a str> {
+    table
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map_or_else(|| vec![key], |(_, aliases)| aliases.to_vec())
+}
+
+// Whether packages can be put in `removal_state` and restored with adb on this SDK
+// (see `apply_pkg_state_commands`)
+const fn removal_supported(android_sdk: u8, removal_state: PackageState, root: bool) -> bool {
+    match removal_state {
+        // Before Android 4.4, packages are really uninstalled and can't be restored
+        PackageState::Uninstalled => android_sdk >= 19,
+        PackageState::Disabled => root || android_sdk >= 23,
+        PackageState::Suspended => android_sdk >= 24,
+        PackageState::Enabled | PackageState::All => false,
+    }
+}
+
+/// Selects the enabled `Recommended` packages relevant for this device:
+/// the OEM packages of its brand, the packages of its carrier(s) and Google packages.
+///
+/// Nothing is recommended if the removal mode (`removal_state`) is not available on the
+/// SDK of the device.
+pub fn recommend(
+    packages: &[PackageRow],
+    profile: &DeviceProfile,
+    removal_state: PackageState,
+    root: bool,
+) -> Vec<Recommendation> {
+    if !removal_supported(profile.android_sdk, removal_state, root) {
+        return vec![];
+    }
+    let brand_aliases = aliases(&BRAND_ALIASES, &profile.brand);
+    let carriers: Vec<(&str, String)> = profile
+        .carriers
+        .iter()
+        .map(|c| (c.as_str(), carrier_token(c)))
+        .filter(|(_, token)| token.len() >= 2)
+        .collect();
+
+    packages
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| p.state == PackageState::Enabled && p.removal == Removal::Recommended)
+        .filter_map(|(index, p)| {
+            let segments: Vec<&str> = p.name.split('.').collect();
+            let reason = match p.uad_list {
+                UadList::Google => Some(tr("Google app recommended for removal")),
+                UadList::Oem
+                    if !profile.brand.is_empty()
+                        && segments.iter().any(|s| brand_aliases.contains(s)) =>
+                {
+                    Some(tr("Bloatware of the manufacturer ({})").replacen("{}", &profile.brand, 1))
+                }
+                // A segment must be the carrier: `att` is not in `com.attractions`
+                UadList::Carrier => carriers.iter().find_map(|(carrier, token)| {
+                    aliases(&CARRIER_ALIASES, token)
+                        .iter()
+                        .any(|alias| segments.contains(alias))
+                        .then(|| tr("Bloatware of your carrier ({})").replacen("{}", carrier, 1))
+                }),
+                _ => None,
+            }?;
+            Some(Recommendation {
+                index,
+                name: p.name.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Package states once the recommendations are applied: the profile a device is compared to
+pub fn recommended_states(
+    packages: &[PackageRow],
+    profile: &DeviceProfile,
+    removal_state: PackageState,
+    root: bool,
+) -> HashMap<String, PackageState> {
+    let mut states: HashMap<String, PackageState> =
+        packages.iter().map(|p| (p.name.clone(), p.state)).collect();
+    for r in recommend(packages, profile, removal_state, root) {
+        states.insert(r.name, removal_state);
+    }
+    states
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::uad_lists::NetworkState;
+
+    #[test]
+    fn test_recommend() {
+        let package = |name, uad_list, removal| {
+            PackageRow::new(
+                name,
+                PackageState::Enabled,
+                NetworkState::Allowed,
+                "",
+                uad_list,
+                removal,
+                false,
+                false,
+            )
+        };
+        let packages = [
+            package(
+                "com.sec.android.app.kidshome",
+                UadList::Oem,
+                Removal::Recommended,
+            ),
+            package("com.huawei.appmarket", UadList::Oem, Removal::Recommended),
+            package("com.samsung.android.bixby", UadList::Oem, Removal::Advanced),
+            package(
+                "com.tmobile.pr.adapt",
+                UadList::Carrier,
+                Removal::Recommended,
+            ),
+            package(
+                "com.vzw.hss.myverizon",
+                UadList::Carrier,
+                Removal::Recommended,
+            ),
+            package(
+                "com.google.android.videos",
+                UadList::Google,
+                Removal::Recommended,
+            ),
+            package(
+                "com.attractions.guide",
+                UadList::Carrier,
+                Removal::Recommended,
+            ),
+        ];
+        let mut profile = DeviceProfile {
+            brand: "samsung".to_string(),
+            carriers: vec!["T-Mobile".to_string(), "AT&T".to_string()],
+            android_sdk: 31,
+        };
+        let recommended: Vec<usize> =
+            recommend(&packages, &profile, PackageState::Uninstalled, false)
+                .iter()
+                .map(|r| r.index)
+                .collect();
+        assert_eq!(recommended, vec![0, 3, 5]);
+
+        // Suspending packages needs Android 7.0
+        profile.android_sdk = 23;
+        assert!(recommend(&packages, &profile, PackageState::Suspended, false).is_empty());
+        assert_eq!(
+            recommend(&packages, &profile, PackageState::Disabled, false).len(),
+            3
+        );
+
+        let states = recommended_states(&packages, &profile, PackageState::Disabled, false);
+        assert_eq!(states.len(), packages.len());
+        assert_eq!(states["com.tmobile.pr.adapt"], PackageState::Disabled);
+        assert_eq!(states["com.samsung.android.bixby"], PackageState::Enabled);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::core::sync::adb_shell_command;
+
+// Magisk module hiding system apps from their partition (systemless debloat)
+pub const MODULE_DIR: &str = "/data/adb/modules/uad_debloat";
+
+// Partitions Magisk can overlay from the `system` directory of a module
+const OVERLAY_PARTITIONS: [&str; 4] = ["/system/", "/product/", "/vendor/", "/system_ext/"];
+
+/// Wraps a shell command to run it as root
+pub fn su(command: &str) -> String {
+    format!("su -c '{command}'")
+}
+
+/// `su` is available and allowed for the adb shell.
+///
+/// The root manager may ask for a confirmation on the device the first time.
+pub async fn detect_root() -> bool {
+    adb_shell_command(true, &su("id")).is_ok_and(|o| o.contains("uid=0"))
+}
+
+// `/product/app/Foo` -> `/data/adb/modules/uad_debloat/system/product/app/Foo`
+fn overlay_dir(code_path: &str) -> Option<String> {
+    // Old devices have APK files directly in `/system/app`: they can't be replaced
+    if code_path.ends_with(".apk") || code_path.matches('/').count() < 3 {
+        return None;
+    }
+    let partition = OVERLAY_PARTITIONS
+        .iter()
+        .find(|p| code_path.starts_with(*p))?;
+    Some(if *partition == "/system/" {
+        format!("{MODULE_DIR}{code_path}")
+    } else {
+        format!("{MODULE_DIR}/system{code_path}")
+    })
+}
+
+/// Command removing a system app from its partition at the next reboot, with an empty
+/// directory replacing it in the Magisk module. `None` if the app can't be overlaid.
+pub fn overlay_remove_command(code_path: &str) -> Option<String> {
+    let dir = overlay_dir(code_path)?;
+    Some(su(&format!(
+        "mkdir -p {dir} && touch {dir}/.replace && printf \"id=uad_debloat\\n\
+        name=UAD debloat\\nversion=1\\nversionCode=1\\nauthor=UAD\\n\
+        description=System apps removed by Universal Android Debloater\\n\" > {MODULE_DIR}/module.prop"
+    )))
+}
+
+/// Command putting back a system app removed by `overlay_remove_command` at the next reboot
+pub fn overlay_restore_command(code_path: &str) -> Option<String> {
+    overlay_dir(code_path).map(|dir| su(&format!("rm -rf {dir}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_dir() {
+        assert_eq!(
+            overlay_dir("/system/priv-app/Foo").as_deref(),
+            Some("/data/adb/modules/uad_debloat/system/priv-app/Foo")
+        );
+        assert_eq!(
+            overlay_dir("/product/app/Bar").as_deref(),
+            Some("/data/adb/modules/uad_debloat/system/product/app/Bar")
+        );
+        assert_eq!(overlay_dir("/system/app/Foo.apk"), None);
+        assert_eq!(overlay_dir("/data/app/~~xyz==/com.foo-1"), None);
+        assert_eq!(overlay_dir("/system/"), None);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::core::sync::adb_shell_command;
+use crate::core::uad_lists::{Package, Removal};
+use crate::CACHE_DIR;
+use iced::futures::channel::oneshot;
+use retry::{delay::Fixed, retry, OperationResult};
+use static_init::dynamic;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Instant;
+
+#[dynamic]
+pub static RECOVERY_DIR: PathBuf = CACHE_DIR.join("recovery");
+
+/// Label of the list entries that must never be removed
+pub const BOOT_CRITICAL_LABEL: &str = "boot-critical";
+
+// Packages without which Android doesn't boot, whatever the debloat list says
+const BOOT_CRITICAL: [&str; 12] = [
+    "android",
+    "com.android.systemui",
+    "com.android.phone",
+    "com.android.settings",
+    "com.android.shell",
+    "com.android.providers.settings",
+    "com.android.providers.telephony",
+    "com.android.server.telecom",
+    "com.android.packageinstaller",
+    "com.google.android.packageinstaller",
+    "com.android.permissioncontroller",
+    "com.google.android.permissioncontroller",
+];
+
+// How long `reboot_and_wait` waits for the device
+const BOOT_TIMEOUT_SECS: u64 = 300;
+
+/// Packages whose removal needs a typed confirmation: the hard-coded ones, the `Unsafe`
+/// ones and the ones labelled `boot-critical` in the debloat list.
+pub fn is_boot_critical(name: &str, package: Option<&Package>) -> bool {
+    BOOT_CRITICAL.contains(&name)
+        || package.is_some_and(|p| {
+            p.removal == Removal::Unsafe || p.labels().iter().any(|l| l == BOOT_CRITICAL_LABEL)
+        })
+}
+
+/// Writes a shell script running `commands` on the device as soon as adb can reach it.
+///
+/// adb is often available before the end of a boot, even when the device bootloops.
+pub fn write_recovery_script(device_id: &str, commands: &[String]) -> Result<PathBuf, String> {
+    fs::create_dir_all(&*RECOVERY_DIR).map_err(|e| e.to_string())?;
+    let date = chrono::Local::now();
+    let path = RECOVERY_DIR.join(format!(
+        "{}_{}.sh",
+        device_id,
+        date.format("%Y-%m-%d_%H-%M-%S")
+    ));
+
+    let mut script = format!(
+        "#!/bin/sh\n\
+        # Restores the packages changed by UAD on {}\n\
+        # Each line can also be copied in a terminal (Windows included).\n\
+        adb -s {device_id} wait-for-device\n",
+        date.format("%Y-%m-%d %H:%M:%S")
+    );
+    for command in commands {
+        script.push_str(&format!("adb -s {device_id} shell {command}\n"));
+    }
+    fs::write(&path, script).map_err(|e| e.to_string())?;
+    info!("[TRIAL] Recovery script written to {}", path.display());
+    Ok(path)
+}
+
+fn boot_completed() -> bool {
+    adb_shell_command(true, "getprop sys.boot_completed").is_ok_and(|o| o.trim() == "1")
+}
+
+/// Reboots the device and waits for Android to finish booting.
+///
+/// Returns the boot duration in seconds.
+pub async fn reboot_and_wait() -> Result<u64, String> {
+    // The wait lasts minutes: it can't hold a thread of the executor
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        // Nobody is waiting anymore if the receiver is dropped
+        let _ = sender.send(wait_for_reboot());
+    });
+    receiver
+        .await
+        .map_err(|_| "The reboot watchdog stopped".to_string())?
+}
+
+fn wait_for_reboot() -> Result<u64, String> {
+    let start = Instant::now();
+    adb_shell_command(false, "reboot")?;
+    info!("[TRIAL] Rebooting the device...");
+
+    // The device may answer for a few seconds after the `reboot` command
+    retry(Fixed::from_millis(1000).take(60), || {
+        if boot_completed() {
+            OperationResult::Retry(())
+        } else {
+            OperationResult::Ok(())
+        }
+    })
+    .map_err(|_| "The device didn't reboot".to_string())?;
+
+    let tries = (BOOT_TIMEOUT_SECS / 2) as usize;
+    retry(Fixed::from_millis(2000).take(tries), || {
+        if boot_completed() {
+            OperationResult::Ok(())
+        } else {
+            OperationResult::Retry(())
+        }
+    })
+    .map_err(|_| format!("The device didn't finish booting within {BOOT_TIMEOUT_SECS} seconds"))?;
+
+    let duration = start.elapsed().as_secs();
+    info!("[TRIAL] The device booted in {} seconds", duration);
+    Ok(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_boot_critical() {
+        const DATA: &str = r#"[
+            {"id": "com.foo", "list": "Oem", "description": "", "dependencies": [],
+             "neededBy": [], "labels": ["boot-critical"], "removal": "Expert"},
+            {"id": "com.bar", "list": "Oem", "description": "", "dependencies": [],
+             "neededBy": [], "labels": [], "removal": "Unsafe"},
+            {"id": "com.baz", "list": "Oem", "description": "", "dependencies": [],
+             "neededBy": [], "labels": [], "removal": "Recommended"}
+        ]"#;
+        let packages: Vec<Package> = serde_json::from_str(DATA).unwrap();
+        assert!(is_boot_critical("com.android.systemui", None));
+        assert!(is_boot_critical("com.foo", Some(&packages[0])));
+        assert!(is_boot_critical("com.bar", Some(&packages[1])));
+        assert!(!is_boot_critical("com.baz", Some(&packages[2])));
+    }
+}
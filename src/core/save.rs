@@ -1,10 +1,14 @@
 use crate::core::config::DeviceSettings;
-use crate::core::sync::{apply_pkg_state_commands, CorePackage, Phone, User};
+use crate::core::sync::{
+    apply_component_commands, apply_pkg_state_commands, CorePackage, Phone, User,
+};
+use crate::core::uad_lists::PackageState;
 use crate::core::utils::DisplayablePath;
 use crate::gui::widgets::package_row::PackageRow;
 use crate::CACHE_DIR;
 use serde::{Deserialize, Serialize};
 use static_init::dynamic;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -41,10 +45,7 @@ pub async fn backup_phone(
         };
 
         for p in phone_packages[u.index].clone() {
-            user_backup.packages.push(CorePackage {
-                name: p.name.clone(),
-                state: p.state,
-            });
+            user_backup.packages.push(p.into());
         }
         backup.users.push(user_backup);
     }
@@ -81,6 +82,27 @@ pub fn list_available_backups(dir: &Path) -> Vec<DisplayablePath> {
     }
 }
 
+/// Backups of all the devices
+pub fn list_all_backups() -> Vec<DisplayablePath> {
+    list_available_backups(&BACKUP_DIR)
+        .into_iter()
+        .filter(|d| d.path.is_dir())
+        .flat_map(|d| list_available_backups(&d.path))
+        .collect()
+}
+
+/// States of the packages of the first user of a backup
+pub fn backup_packages_state(backup: &Path) -> Result<HashMap<String, PackageState>, String> {
+    let data = fs::read_to_string(backup).map_err(|e| e.to_string())?;
+    let phone_backup: PhoneBackup = serde_json::from_str(&data).map_err(|e| e.to_string())?;
+    Ok(phone_backup
+        .users
+        .into_iter()
+        .next()
+        .map(|u| u.packages.into_iter().map(|p| (p.name, p.state)).collect())
+        .unwrap_or_default())
+}
+
 pub fn list_available_backup_user(backup: DisplayablePath) -> Vec<User> {
     match fs::read_to_string(backup.path) {
         Ok(data) => {
@@ -91,8 +113,7 @@ pub fn list_available_backup_user(backup: DisplayablePath) -> Vec<User> {
             for u in phone_backup.users {
                 users.push(User {
                     id: u.id,
-                    index: 0,
-                    protected: false,
+                    ..User::default()
                 });
             }
             users
@@ -149,15 +170,41 @@ pub fn restore_backup(
                             ))
                         }
                     }
-                    let p_commands = apply_pkg_state_commands(
+                    let selected_user = settings
+                        .backup
+                        .selected_user
+                        .clone()
+                        .ok_or("field should be Some type")?;
+                    let mut p_commands = apply_pkg_state_commands(
                         &package,
                         backup_package.state,
-                        &settings
-                            .backup
-                            .selected_user
-                            .ok_or("field should be Some type")?,
+                        &selected_user,
                         selected_device,
+                        settings.root_mode,
                     );
+                    if let Some(wanted) = &backup_package.disabled_components {
+                        let current = package.disabled_components.unwrap_or_default();
+                        for c in wanted.iter().filter(|c| !current.contains(c)) {
+                            p_commands.extend(apply_component_commands(
+                                &package.name,
+                                c,
+                                false,
+                                &selected_user,
+                                selected_device,
+                                settings.root_mode,
+                            ));
+                        }
+                        for c in current.iter().filter(|c| !wanted.contains(c)) {
+                            p_commands.extend(apply_component_commands(
+                                &package.name,
+                                c,
+                                true,
+                                &selected_user,
+                                selected_device,
+                                settings.root_mode,
+                            ));
+                        }
+                    }
                     if !p_commands.is_empty() {
                         commands.push(BackupPackage {
                             index: i,
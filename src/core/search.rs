@@ -0,0 +1,158 @@
+use crate::core::uad_lists::{NetworkState, PackageState, Removal, UadList};
+use crate::gui::widgets::package_row::PackageRow;
+
+/// Parsed content of the search bar.
+///
+/// `state:disabled removal:expert oem samsung` keeps the disabled packages marked as
+/// `Expert` matching both `oem` and `samsung`. Filters override the matching pick lists
+/// (`state:all` shows the packages in any state).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub state: Option<PackageState>,
+    pub removal: Option<Removal>,
+    pub list: Option<UadList>,
+    pub network: Option<NetworkState>,
+    pub terms: Vec<String>, // lowercase
+}
+
+// Filters use the variant names, whatever the language of the GUI
+fn find_variant<T: std::fmt::Debug + Copy>(variants: &[T], value: &str) -> Option<T> {
+    variants
+        .iter()
+        .find(|v| format!("{v:?}").eq_ignore_ascii_case(value))
+        .copied()
+}
+
+pub fn parse_query(input: &str) -> Query {
+    let mut query = Query::default();
+    for word in input.split_whitespace() {
+        let filter = word.split_once(':').and_then(|(key, value)| {
+            match key.to_lowercase().as_str() {
+                "state" => query.state = Some(find_variant(&PackageState::ALL, value)?),
+                "removal" => query.removal = Some(find_variant(&Removal::ALL, value)?),
+                "list" => query.list = Some(find_variant(&UadList::ALL, value)?),
+                "network" => query.network = Some(find_variant(&NetworkState::ALL, value)?),
+                _ => return None,
+            }
+            Some(())
+        });
+        // Unknown filters are searched as is
+        if filter.is_none() {
+            query.terms.push(word.to_lowercase());
+        }
+    }
+    query
+}
+
+impl Query {
+    /// Returns `None` if the package doesn't match the query, its relevance otherwise.
+    pub fn score(&self, package: &PackageRow) -> Option<u32> {
+        if !(matches(self.state, PackageState::All, package.state)
+            && matches(self.removal, Removal::All, package.removal)
+            && matches(self.list, UadList::All, package.uad_list)
+            && matches(self.network, NetworkState::All, package.network))
+        {
+            return None;
+        }
+
+        let name = package.name.to_lowercase();
+        let apk_name = package.apk_name.to_lowercase();
+        let list = format!("{:?}", package.uad_list).to_lowercase();
+        let description = package.description.to_lowercase();
+
+        // Every term has to match at least one field
+        self.terms.iter().try_fold(0, |total, term| {
+            let best = [
+                fuzzy_score(term, &name).map(|s| s * 3),
+                fuzzy_score(term, &apk_name).map(|s| s * 2),
+                (list == *term).then_some(200),
+                description.contains(term.as_str()).then_some(50),
+            ]
+            .into_iter()
+            .flatten()
+            .max()?;
+            Some(total + best)
+        })
+    }
+}
+
+fn matches<T: PartialEq>(filter: Option<T>, all: T, value: T) -> bool {
+    filter.is_none_or(|f| f == all || f == value)
+}
+
+/// Scores how well `needle` matches `haystack` (both lowercase).
+///
+/// Substrings are preferred, especially at the start of a segment (`bixby` in
+/// `com.samsung.android.bixby.agent`). Otherwise the characters of `needle` must appear in
+/// order: consecutive characters score more than scattered ones.
+fn fuzzy_score(needle: &str, haystack: &str) -> Option<u32> {
+    if let Some(i) = haystack.find(needle) {
+        let segment_start = i == 0 || !haystack.as_bytes()[i - 1].is_ascii_alphanumeric();
+        return Some(if segment_start { 120 } else { 100 });
+    }
+    // Short needles would match almost everything
+    if needle.chars().count() < 3 {
+        return None;
+    }
+
+    let mut chars = haystack.chars();
+    let (mut score, mut streak) = (0, 0);
+    for c in needle.chars() {
+        let mut gap = false;
+        loop {
+            match chars.next() {
+                Some(h) if h == c => break,
+                Some(_) => gap = true,
+                None => return None,
+            }
+        }
+        streak = if gap { 1 } else { streak + 1 };
+        score += streak;
+    }
+    // Always below a substring match
+    Some(score.min(90))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query() {
+        let query = parse_query("state:disabled REMOVAL:Expert  oem foo:bar list:nope");
+        assert_eq!(query.state, Some(PackageState::Disabled));
+        assert_eq!(query.removal, Some(Removal::Expert));
+        assert_eq!(query.list, None);
+        assert_eq!(query.terms, vec!["oem", "foo:bar", "list:nope"]);
+        assert_eq!(parse_query("  "), Query::default());
+
+        let package = |name, uad_list| {
+            PackageRow::new(
+                name,
+                PackageState::Enabled,
+                NetworkState::Allowed,
+                "Samsung assistant",
+                uad_list,
+                Removal::Advanced,
+                false,
+                false,
+            )
+        };
+        let bixby = package("com.samsung.android.bixby.agent", UadList::Oem);
+        let query = parse_query("oem bixby");
+        assert!(query.score(&bixby).is_some());
+        assert!(query
+            .score(&package("com.samsung.android.bixby.agent", UadList::Misc))
+            .is_none());
+        assert!(parse_query("state:disabled").score(&bixby).is_none());
+        assert!(parse_query("state:all removal:advanced")
+            .score(&bixby)
+            .is_some());
+
+        // Exact match > fuzzy match
+        let fuzzy = parse_query("bxby").score(&bixby).unwrap();
+        assert!(parse_query("bixby").score(&bixby).unwrap() > fuzzy);
+        assert!(parse_query("assistant").score(&bixby).is_some());
+        assert!(parse_query("zzz").score(&bixby).is_none());
+    }
+}
@@ -1,11 +1,18 @@
-use crate::core::uad_lists::PackageState;
+use crate::core::dumpsys::{
+    parse_components, parse_diskstats, parse_packages_dump, parse_users, Component, PackageDump,
+    PackageSize,
+};
+use crate::core::i18n::tr;
+use crate::core::root::su;
+use crate::core::uad_lists::{NetworkState, PackageState};
 use crate::gui::views::list::PackageInfo;
 use crate::gui::widgets::package_row::PackageRow;
 use regex::Regex;
 use retry::{delay::Fixed, retry, OperationResult};
 use serde::{Deserialize, Serialize};
 use static_init::dynamic;
-use std::collections::HashSet;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::process::Command;
 
@@ -15,6 +22,11 @@ use std::os::windows::process::CommandExt;
 #[dynamic]
 static RE: Regex = Regex::new(r"\n(\S+)\s+device").unwrap();
 
+thread_local! {
+    // Device of the adb commands of this thread when it's not the selected one
+    static DEVICE_OVERRIDE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Phone {
     pub model: String,
@@ -40,20 +52,181 @@ impl std::fmt::Display for Phone {
     }
 }
 
-#[derive(Default, Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserKind {
+    #[default]
+    Owner,
+    Secondary,
+    WorkProfile,
+    Clone, // dual apps / app cloning
+    Guest,
+}
+
+impl UserKind {
+    // android.content.pm.UserInfo flags
+    const FLAG_PRIMARY: u32 = 0x1;
+    const FLAG_GUEST: u32 = 0x4;
+    const FLAG_MANAGED_PROFILE: u32 = 0x20;
+    const FLAG_PROFILE: u32 = 0x1000;
+
+    // Android < 11 has no user types: guess them from the flags
+    fn new(id: u16, flags: u32, user_type: &str) -> Self {
+        match user_type {
+            "full.SYSTEM" | "system.HEADLESS" => Self::Owner,
+            "full.GUEST" => Self::Guest,
+            "profile.MANAGED" => Self::WorkProfile,
+            t if t.starts_with("profile.") => Self::Clone,
+            t if t.starts_with("full.") => Self::Secondary,
+            _ if id == 0 || flags & Self::FLAG_PRIMARY != 0 => Self::Owner,
+            _ if flags & Self::FLAG_GUEST != 0 => Self::Guest,
+            _ if flags & Self::FLAG_MANAGED_PROFILE != 0 => Self::WorkProfile,
+            // 999 is the dual apps user of MIUI
+            _ if flags & Self::FLAG_PROFILE != 0 || id == 999 => Self::Clone,
+            _ => Self::Secondary,
+        }
+    }
+
+    /// What the user should know before debloating a user of this kind
+    pub fn warning(self) -> Option<String> {
+        match self {
+            Self::Owner | Self::Secondary => None,
+            Self::WorkProfile => Some(tr(
+                "Work profile: its apps are managed by your organization, removing them can break the profile",
+            )),
+            Self::Clone => Some(tr(
+                "Clone profile: it runs copies of apps of its parent user, system apps are shared with it",
+            )),
+            Self::Guest => Some(tr(
+                "Guest user: its changes are lost when the guest session is removed",
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for UserKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            tr(match self {
+                Self::Owner => "Owner",
+                Self::Secondary => "Secondary user",
+                Self::WorkProfile => "Work profile",
+                Self::Clone => "Clone profile",
+                Self::Guest => "Guest",
+            })
+        )
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct User {
     pub id: u16,
     pub index: usize,
     pub protected: bool,
+    pub name: String,
+    pub kind: UserKind,
+    pub running: bool,
+    pub flags: u32, // android.content.pm.UserInfo flags
 }
 
 impl std::fmt::Display for User {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "user {}", self.id)
+        if self.name.is_empty() {
+            write!(f, "user {}", self.id)
+        } else {
+            write!(f, "{} - {} ({})", self.id, self.name, self.kind)
+        }
+    }
+}
+
+/// Failure of an adb command, classified from its output and exit code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdbError {
+    AdbNotFound,
+    // Errors reported by adb or the device keep their output
+    NoDevice(String),
+    Unauthorized(String),
+    Offline(String),
+    NotInstalled(String),
+    PermissionDenied(String),
+    DeviceChanged,  // the selected device changed before the command ran
+    Failed(String), // device message
+}
+
+impl AdbError {
+    pub fn from_output(output: &str, exit_code: Option<i32>) -> Self {
+        let output = output.trim();
+        let lowercase = output.to_lowercase();
+        let contains = |patterns: &[&str]| patterns.iter().any(|p| lowercase.contains(p));
+
+        // e.g. `error: device 'foo' not found`
+        let missing_device = lowercase.starts_with("error: device") && contains(&["not found"]);
+
+        if missing_device || contains(&["no devices/emulators found"]) {
+            Self::NoDevice(output.to_string())
+        } else if contains(&["unauthorized"]) {
+            Self::Unauthorized(output.to_string())
+        } else if contains(&["device offline", "error: closed", "protocol fault"]) {
+            Self::Offline(output.to_string())
+        } else if contains(&["not installed for", "unknown package", "is not installed"]) {
+            Self::NotInstalled(output.to_string())
+        } else if contains(&[
+            "permission denied",
+            "securityexception",
+            "does not have permission",
+            "not allowed",
+            "protected package",
+        ]) {
+            Self::PermissionDenied(output.to_string())
+        } else if output.is_empty() {
+            Self::Failed(exit_code.map_or_else(
+                || "killed by a signal".to_string(),
+                |code| format!("exit code {code}"),
+            ))
+        } else {
+            Self::Failed(output.to_string())
+        }
+    }
+}
+
+impl std::fmt::Display for AdbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The hint comes first, followed by the real output of the device
+        let with_output = |hint: &str, output: &str| {
+            if output.is_empty() {
+                tr(hint)
+            } else {
+                format!("{} ({output})", tr(hint))
+            }
+        };
+        let message = match self {
+            Self::AdbNotFound => tr("ADB was not found: install the Android platform tools"),
+            Self::NoDevice(output) => with_output("The device is not connected", output),
+            Self::Unauthorized(output) => {
+                with_output("Accept the USB debugging prompt on the device", output)
+            }
+            Self::Offline(output) => with_output("The device is offline: reconnect it", output),
+            Self::NotInstalled(output) => {
+                with_output("The package is not installed for this user", output)
+            }
+            Self::PermissionDenied(output) => {
+                tr("Permission denied (root may be required): {}").replacen("{}", output, 1)
+            }
+            Self::DeviceChanged => tr("The selected device changed before the command ran"),
+            Self::Failed(output) => output.clone(),
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl From<AdbError> for String {
+    fn from(err: AdbError) -> Self {
+        err.to_string()
     }
 }
 
-pub fn adb_shell_command(shell: bool, args: &str) -> Result<String, String> {
+pub fn adb_shell_command(shell: bool, args: &str) -> Result<String, AdbError> {
     let adb_command = if shell {
         vec!["shell", args]
     } else {
@@ -62,6 +235,11 @@ pub fn adb_shell_command(shell: bool, args: &str) -> Result<String, String> {
 
     let mut command = Command::new("adb");
     command.args(adb_command);
+    DEVICE_OVERRIDE.with(|device| {
+        if let Some(serial) = &*device.borrow() {
+            command.env("ANDROID_SERIAL", serial);
+        }
+    });
 
     #[cfg(target_os = "windows")]
     let command = command.creation_flags(0x08000000); // do not open a cmd window
@@ -69,27 +247,18 @@ pub fn adb_shell_command(shell: bool, args: &str) -> Result<String, String> {
     match command.output() {
         Err(e) => {
             error!("ADB: {}", e);
-            Err("ADB was not found".to_string())
+            Err(AdbError::AdbNotFound)
         }
         Ok(o) => {
+            let stdout = String::from_utf8_lossy(&o.stdout).trim_end().to_string();
             if o.status.success() {
-                Ok(String::from_utf8(o.stdout)
-                    .map_err(|e| e.to_string())?
-                    .trim_end()
-                    .to_string())
+                Ok(stdout)
             } else {
-                let stdout = String::from_utf8(o.stdout)
-                    .map_err(|e| e.to_string())?
-                    .trim_end()
-                    .to_string();
-                let stderr = String::from_utf8(o.stderr)
-                    .map_err(|e| e.to_string())?
-                    .trim_end()
-                    .to_string();
+                let stderr = String::from_utf8_lossy(&o.stderr).trim_end().to_string();
 
                 // ADB does really weird things. Some errors are not redirected to stderr
                 let err = if stdout.is_empty() { stderr } else { stdout };
-                Err(err)
+                Err(AdbError::from_output(&err, o.status.code()))
             }
         }
     }
@@ -103,7 +272,7 @@ pub enum CommandType {
 pub async fn perform_adb_commands(
     action: String,
     command_type: CommandType,
-) -> Result<CommandType, ()> {
+) -> Result<CommandType, AdbError> {
     let label = match command_type {
         CommandType::PackageManager(ref p) => p.removal.to_string(),
         CommandType::Shell => "Shell".to_string(),
@@ -118,17 +287,17 @@ pub async fn perform_adb_commands(
             // the output.
             if ["Error", "Failure"].iter().any(|&e| o.contains(e)) {
                 error!("[{}] {} -> {}", label, action, o);
-                Err(())
+                Err(AdbError::from_output(&o, Some(0)))
             } else {
                 info!("[{}] {} -> {}", label, action, o);
                 Ok(command_type)
             }
         }
         Err(err) => {
-            if !err.contains("[not installed for") {
+            if !matches!(err, AdbError::NotInstalled(_)) {
                 error!("[{}] {} -> {}", label, action, err);
             }
-            Err(())
+            Err(err)
         }
     }
 }
@@ -165,11 +334,170 @@ pub fn hashset_system_packages(state: PackageState, user_id: Option<&User>) -> H
         .collect()
 }
 
+pub fn package_state(
+    name: &str,
+    enabled: &HashSet<String>,
+    disabled: &HashSet<String>,
+    suspended: bool,
+) -> PackageState {
+    // Suspended packages are still listed as enabled packages
+    if suspended && enabled.contains(name) {
+        PackageState::Suspended
+    } else if enabled.contains(name) {
+        PackageState::Enabled
+    } else if disabled.contains(name) {
+        PackageState::Disabled
+    } else {
+        PackageState::Uninstalled
+    }
+}
+
+/// State of all the system packages of a user, as reported by the device
+pub fn get_packages_state(user_id: Option<&User>) -> HashMap<String, PackageState> {
+    let enabled = hashset_system_packages(PackageState::Enabled, user_id);
+    let disabled = hashset_system_packages(PackageState::Disabled, user_id);
+    let packages_dump = hashmap_packages_dump();
+    let id = user_id.map_or(0, |u| u.id);
+
+    list_all_system_packages(user_id)
+        .lines()
+        .map(|name| {
+            let suspended = packages_dump
+                .get(name)
+                .and_then(|d| d.users.get(&id))
+                .is_some_and(|u| u.suspended);
+            (
+                name.to_string(),
+                package_state(name, &enabled, &disabled, suspended),
+            )
+        })
+        .collect()
+}
+
+/// States of the packages of a user of a connected device, selected or not
+pub async fn get_device_packages_state(
+    serial: String,
+    user: Option<User>,
+) -> HashMap<String, PackageState> {
+    // `ANDROID_SERIAL` is shared with the commands of the selected device
+    DEVICE_OVERRIDE.with(|device| *device.borrow_mut() = Some(serial));
+    let states = get_packages_state(user.as_ref());
+    DEVICE_OVERRIDE.with(|device| *device.borrow_mut() = None);
+    states
+}
+
+// Package details not available with `pm list packages` (suspended state,
+// disabled components, system updates, ...)
+pub fn hashmap_packages_dump() -> HashMap<String, PackageDump> {
+    parse_packages_dump(&adb_shell_command(true, "dumpsys package packages").unwrap_or_default())
+}
+
+pub async fn get_packages_size() -> HashMap<String, PackageSize> {
+    match adb_shell_command(true, "dumpsys diskstats") {
+        Ok(dump) => parse_diskstats(&dump),
+        Err(err) => {
+            error!("[DISKSTATS] {}", err);
+            HashMap::new()
+        }
+    }
+}
+
+// Size of a file or directory on the device in bytes
+pub fn get_path_size(path: &str) -> u64 {
+    adb_shell_command(true, &format!("du -sk {path}"))
+        .ok()
+        .and_then(|o| o.split_whitespace().next()?.parse::<u64>().ok())
+        .map_or(0, |kb| kb * 1024)
+}
+
+// Free space of the data partition in bytes (`df` of toybox, Android 6.0+)
+pub fn get_data_free_space() -> Option<u64> {
+    adb_shell_command(true, "df -k /data")
+        .ok()?
+        .lines()
+        .last()?
+        .split_whitespace()
+        .nth(3)?
+        .parse::<u64>()
+        .ok()
+        .map(|kb| kb * 1024)
+}
+
+pub async fn get_package_components(package: String, user_id: u16) -> Vec<Component> {
+    match adb_shell_command(true, &format!("dumpsys package {package}")) {
+        Ok(dump) => parse_components(&dump, &package, user_id),
+        Err(err) => {
+            error!("[COMPONENTS] {} -> {}", package, err);
+            vec![]
+        }
+    }
+}
+
+// Network restrictions are tracked through the `restrict background` denylist of
+// the network policy service because it's the only one that can be listed in one
+// adb call. `cmd connectivity` (Android 13+) can only be queried package by package.
+// The background data denylist is the network state set by UAD: on Android 13+ packages
+// are added to it along with their networking being disabled (see `apply_network_commands`)
+pub fn hashset_network_blocked_packages(
+    user_id: Option<&User>,
+    android_sdk: u8,
+) -> HashSet<String> {
+    // Uids are listed by `pm list packages -U` since Android 8.0
+    if android_sdk < 26 {
+        return HashSet::new();
+    }
+
+    let blocked_uids = restricted_background_uids().unwrap_or_default();
+    if blocked_uids.is_empty() {
+        return HashSet::new();
+    }
+
+    adb_shell_command(
+        true,
+        &format!("pm list packages -s -U{}", user_flag(user_id)),
+    )
+    .unwrap_or_default()
+    .lines()
+    .filter_map(|line| {
+        let (name, uid) = line.strip_prefix("package:")?.split_once(" uid:")?;
+        blocked_uids.contains(uid.trim()).then(|| name.to_string())
+    })
+    .collect()
+}
+
+// Uids in the background data denylist of the network policy service
+fn restricted_background_uids() -> Result<HashSet<String>, AdbError> {
+    Ok(
+        adb_shell_command(true, "cmd netpolicy list restrict-background-blacklist")?
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|uid| !uid.is_empty())
+            .map(ToString::to_string)
+            .collect(),
+    )
+}
+
+pub fn get_package_uid(package: &str, user_id: Option<&User>) -> Option<String> {
+    // `pm list packages` treats the package name as a substring filter
+    adb_shell_command(
+        true,
+        &format!("pm list packages -U{} {package}", user_flag(user_id)),
+    )
+    .ok()?
+    .lines()
+    .find_map(|line| {
+        let (name, uid) = line.strip_prefix("package:")?.split_once(" uid:")?;
+        (name == package).then(|| uid.trim().to_string())
+    })
+}
+
 // Minimum information for processing adb commands
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct CorePackage {
     pub name: String,
     pub state: PackageState,
+    // `None` in backups made before components could be disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_components: Option<Vec<String>>,
 }
 
 impl From<&mut PackageRow> for CorePackage {
@@ -177,6 +505,7 @@ impl From<&mut PackageRow> for CorePackage {
         Self {
             name: pr.name.clone(),
             state: pr.state,
+            disabled_components: Some(pr.disabled_components.clone()),
         }
     }
 }
@@ -185,6 +514,7 @@ impl From<PackageRow> for CorePackage {
         Self {
             name: pr.name.clone(),
             state: pr.state,
+            disabled_components: Some(pr.disabled_components.clone()),
         }
     }
 }
@@ -194,6 +524,7 @@ impl From<&PackageRow> for CorePackage {
         Self {
             name: pr.name.clone(),
             state: pr.state,
+            disabled_components: Some(pr.disabled_components.clone()),
         }
     }
 }
@@ -203,6 +534,7 @@ pub fn apply_pkg_state_commands(
     wanted_state: PackageState,
     selected_user: &User,
     phone: &Phone,
+    root: bool,
 ) -> Vec<String> {
     // https://github.com/0x192/universal-android-debloater/wiki/ADB-reference
     // ALWAYS PUT THE COMMAND THAT CHANGES THE PACKAGE STATE FIRST!
@@ -219,32 +551,205 @@ pub fn apply_pkg_state_commands(
                     19 | 20 => vec!["pm unblock", "pm clear"],
                     _ => vec![], // Impossible action already prevented by the GUI
                 },
+                PackageState::Suspended => vec!["pm unsuspend"],
                 _ => vec![],
             }
         }
+        // Root can really disable packages (`disable-user` only hides them from the user),
+        // even on old devices
+        PackageState::Disabled if root => match package.state {
+            PackageState::Uninstalled | PackageState::Enabled => {
+                vec!["pm disable", "am force-stop", "pm clear"]
+            }
+            PackageState::Suspended => {
+                vec!["pm disable", "am force-stop", "pm clear", "pm unsuspend"]
+            }
+            _ => vec![],
+        },
         PackageState::Disabled => match package.state {
             PackageState::Uninstalled | PackageState::Enabled => match phone.android_sdk {
                 sdk if sdk >= 23 => vec!["pm disable-user", "am force-stop", "pm clear"],
                 _ => vec![],
             },
+            PackageState::Suspended => match phone.android_sdk {
+                sdk if sdk >= 23 => vec![
+                    "pm disable-user",
+                    "am force-stop",
+                    "pm clear",
+                    "pm unsuspend",
+                ],
+                _ => vec![],
+            },
             _ => vec![],
         },
-        PackageState::Uninstalled => match package.state {
-            PackageState::Enabled | PackageState::Disabled => match phone.android_sdk {
-                sdk if sdk >= 23 => vec!["pm uninstall"], // > Android Marshmallow (6.0)
-                21 | 22 => vec!["pm hide", "pm clear"],   // Android Lollipop (5.x)
-                19 | 20 => vec!["pm block", "pm clear"],  // Android KitKat (4.4/4.4W)
-                _ => vec!["pm uninstall"], // Disable mode is unavailable on older devices because the specific ADB commands need root
+        // Suspending packages is available since Android Nougat (7.0)
+        PackageState::Suspended => match package.state {
+            PackageState::Enabled => match phone.android_sdk {
+                sdk if sdk >= 24 => vec!["pm suspend"],
+                _ => vec![],
+            },
+            PackageState::Disabled => match phone.android_sdk {
+                sdk if sdk >= 24 => vec!["pm enable", "pm suspend"],
+                _ => vec![],
             },
+            PackageState::Uninstalled => match phone.android_sdk {
+                sdk if sdk >= 24 => vec!["cmd package install-existing", "pm suspend"],
+                _ => vec![],
+            },
+            _ => vec![],
+        },
+        PackageState::Uninstalled => match package.state {
+            PackageState::Enabled | PackageState::Disabled | PackageState::Suspended => {
+                match phone.android_sdk {
+                    sdk if sdk >= 23 => vec!["pm uninstall"], // > Android Marshmallow (6.0)
+                    21 | 22 => vec!["pm hide", "pm clear"],   // Android Lollipop (5.x)
+                    19 | 20 => vec!["pm block", "pm clear"],  // Android KitKat (4.4/4.4W)
+                    _ => vec!["pm uninstall"], // Disable mode is unavailable on older devices because the specific ADB commands need root
+                }
+            }
             _ => vec![],
         },
         PackageState::All => vec![],
     };
-    if phone.android_sdk < 21 {
+    let requests = if phone.android_sdk < 21 {
         request_builder(&commands, &package.name, None)
     } else {
         request_builder(&commands, &package.name, Some(selected_user))
+    };
+    // Also clears the data of protected packages
+    if root {
+        requests.iter().map(|r| su(r)).collect()
+    } else {
+        requests
+    }
+}
+
+// `uid` of the package is given by `get_package_uid`
+pub fn apply_network_commands(
+    package: &CorePackage,
+    wanted_state: NetworkState,
+    uid: Option<&str>,
+    phone: &Phone,
+) -> Vec<String> {
+    let enabled = wanted_state != NetworkState::Blocked;
+    let mut commands = vec![];
+
+    // ALWAYS PUT THE COMMAND THAT CHANGES THE NETWORK STATE FIRST!
+    // `set-package-networking-enabled` is only available since Android 13
+    if phone.android_sdk >= 33 {
+        commands.push(format!(
+            "cmd connectivity set-package-networking-enabled {enabled} {}",
+            package.name
+        ));
+    }
+    // Before Android 13 only background data can be restricted (`cmd netpolicy`, Android 7.0+).
+    // The uid of the package is needed, which `pm list packages -U` gives since Android 8.0.
+    if phone.android_sdk >= 26 {
+        match uid {
+            Some(uid) => commands.push(format!(
+                "cmd netpolicy {} restrict-background-blacklist {uid}",
+                if enabled { "remove" } else { "add" }
+            )),
+            None => warn!("[NETWORK] Could not find the uid of {}", package.name),
+        }
     }
+    commands
+}
+
+/// Changes the network state of a package, then reads it back from the device once all the
+/// commands ran (`None` if it can't be read). The result is the one of the first failed command.
+pub async fn perform_network_commands(
+    package: CorePackage,
+    wanted_state: NetworkState,
+    selected_user: User,
+    phone: Phone,
+    command_type: CommandType,
+) -> (CommandType, Option<NetworkState>, Result<(), AdbError>) {
+    let user = (phone.android_sdk >= 21).then_some(&selected_user);
+    // `pm list packages -U` is also an adb call: not in the GUI thread
+    let uid = if phone.android_sdk >= 26 {
+        get_package_uid(&package.name, user)
+    } else {
+        None
+    };
+    let mut result = Ok(());
+    for action in apply_network_commands(&package, wanted_state, uid.as_deref(), &phone) {
+        if let Err(err) = perform_adb_commands(action, command_type.clone()).await {
+            result = result.and(Err(err));
+        }
+    }
+    if uid.is_none() && phone.android_sdk >= 26 {
+        result = result.and(Err(AdbError::Failed(tr(
+            "The uid of the package was not found",
+        ))));
+    }
+    let state = uid.and_then(|uid| {
+        let blocked = restricted_background_uids().ok()?.contains(&uid);
+        Some(if blocked {
+            NetworkState::Blocked
+        } else {
+            NetworkState::Allowed
+        })
+    });
+    (command_type, state, result)
+}
+
+// Components are disabled with `pm disable <package>/<class>`, the same command as
+// for whole packages. The shell user can't change the state of a component (only of whole
+// packages, with `disable-user`) so it needs root. No command without root.
+pub fn apply_component_commands(
+    package: &str,
+    component: &str,
+    enable: bool,
+    selected_user: &User,
+    phone: &Phone,
+    root: bool,
+) -> Vec<String> {
+    if !root {
+        return vec![];
+    }
+    let command = if enable { "pm enable" } else { "pm disable" };
+    let component = format!("{package}/{component}");
+
+    if phone.android_sdk < 21 {
+        request_builder(&[command], &component, None)
+    } else {
+        request_builder(&[command], &component, Some(selected_user))
+    }
+    .iter()
+    .map(|c| su(c))
+    .collect()
+}
+
+// Reverts an updated system app to its factory version. Updates are
+// installed for all users so the command doesn't need any user flag.
+pub fn apply_uninstall_updates_commands(package: &CorePackage, phone: &Phone) -> Vec<String> {
+    match phone.android_sdk {
+        // Before Android 12, `uninstall-system-updates` doesn't take any package
+        // and removes the updates of ALL system apps!
+        sdk if sdk >= 31 => vec![format!("pm uninstall-system-updates {}", package.name)],
+        // Uninstalling an updated system app for all users only removes its update
+        sdk if sdk >= 21 => vec![format!("pm uninstall {}", package.name)],
+        _ => vec![],
+    }
+}
+
+/// Reclaimed bytes: the size of the update, once it's really gone from the device
+pub async fn perform_uninstall_updates(
+    action: String,
+    update_path: String,
+    command_type: CommandType,
+) -> (CommandType, Result<u64, AdbError>) {
+    // Measured beforehand as the update is removed by the command
+    let size = get_path_size(&update_path);
+    let res = match perform_adb_commands(action, command_type.clone()).await {
+        Ok(_) if get_path_size(&update_path) > 0 => {
+            Err(AdbError::Failed(tr("The update is still on the device")))
+        }
+        Ok(_) => Ok(size),
+        Err(err) => Err(err),
+    };
+    (command_type, res)
 }
 
 pub fn request_builder(commands: &[&str], package: &str, user: Option<&User>) -> Vec<String> {
@@ -261,10 +766,10 @@ pub fn request_builder(commands: &[&str], package: &str, user: Option<&User>) ->
 pub fn get_phone_model() -> String {
     adb_shell_command(true, "getprop ro.product.model").unwrap_or_else(|err| {
         println!("ERROR: {err}");
-        if err.contains("adb: no devices/emulators found") {
+        if matches!(err, AdbError::NoDevice(_)) {
             "no devices/emulators found".to_string()
         } else {
-            err
+            err.to_string()
         }
     })
 }
@@ -288,20 +793,24 @@ pub fn is_protected_user(user_id: &str) -> bool {
 }
 
 pub fn get_user_list() -> Vec<User> {
-    #[dynamic]
-    static RE: Regex = Regex::new(r"\{([0-9]+)").unwrap();
-    adb_shell_command(true, "pm list users")
-        .map(|users| {
-            RE.find_iter(&users)
-                .enumerate()
-                .map(|(i, u)| User {
-                    id: u.as_str()[1..].parse().unwrap(),
-                    index: i,
-                    protected: is_protected_user(&u.as_str()[1..]),
-                })
-                .collect()
+    let Ok(pm_list_users) = adb_shell_command(true, "pm list users") else {
+        return vec![];
+    };
+    // Types and states of users are more accurate in `dumpsys user`
+    let dumpsys_user = adb_shell_command(true, "dumpsys user").unwrap_or_default();
+    parse_users(&pm_list_users, &dumpsys_user)
+        .into_iter()
+        .enumerate()
+        .map(|(i, u)| User {
+            id: u.id,
+            index: i,
+            protected: is_protected_user(&u.id.to_string()),
+            kind: UserKind::new(u.id, u.flags, &u.user_type),
+            name: u.name,
+            running: u.running,
+            flags: u.flags,
         })
-        .unwrap_or_default()
+        .collect()
 }
 
 // getprop ro.serialno
@@ -334,3 +843,61 @@ pub async fn get_devices_list() -> Vec<Phone> {
     )
     .unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adb_error() {
+        let cases: [(&str, fn(String) -> AdbError); 7] = [
+            ("adb: no devices/emulators found", AdbError::NoDevice),
+            ("error: device 'R58M' not found", AdbError::NoDevice),
+            ("error: device unauthorized.", AdbError::Unauthorized),
+            ("error: device offline", AdbError::Offline),
+            ("Failure [not installed for 10]", AdbError::NotInstalled),
+            (
+                "Error: java.lang.IllegalArgumentException: Unknown package: com.foo",
+                AdbError::NotInstalled,
+            ),
+            ("Failure [DELETE_FAILED_INTERNAL_ERROR]", AdbError::Failed),
+        ];
+        for (output, err) in cases {
+            let error = AdbError::from_output(output, Some(1));
+            assert_eq!(error, err(output.to_string()), "{output}");
+            // The output of the device is never hidden
+            assert!(error.to_string().contains(output), "{output}");
+        }
+        assert_eq!(
+            AdbError::from_output("", Some(1)),
+            AdbError::Failed("exit code 1".to_string())
+        );
+        assert!(matches!(
+            AdbError::from_output(
+                "java.lang.SecurityException: Shell does not have permission to access user 150",
+                Some(255)
+            ),
+            AdbError::PermissionDenied(_)
+        ));
+    }
+
+    #[test]
+    fn test_user_kind() {
+        assert_eq!(UserKind::new(0, 0x13, "full.SYSTEM"), UserKind::Owner);
+        assert_eq!(
+            UserKind::new(10, 0x410, "full.SECONDARY"),
+            UserKind::Secondary
+        );
+        assert_eq!(UserKind::new(10, 0x414, "full.GUEST"), UserKind::Guest);
+        assert_eq!(
+            UserKind::new(11, 0x1030, "profile.MANAGED"),
+            UserKind::WorkProfile
+        );
+        assert_eq!(UserKind::new(95, 0x1010, "profile.CLONE"), UserKind::Clone);
+        // Android < 11
+        assert_eq!(UserKind::new(0, 0x13, ""), UserKind::Owner);
+        assert_eq!(UserKind::new(10, 0x30, ""), UserKind::WorkProfile);
+        assert_eq!(UserKind::new(999, 0x10, ""), UserKind::Clone);
+        assert_eq!(UserKind::new(12, 0x10, ""), UserKind::Secondary);
+    }
+}
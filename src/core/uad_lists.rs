@@ -1,11 +1,14 @@
+use crate::core::i18n::{current_language, tr};
 use crate::core::utils::{format_diff_time_from_now, last_modified_date};
 use crate::CACHE_DIR;
-use retry::{delay::Fixed, retry, OperationResult};
+use minisign_verify::{PublicKey, Signature};
+use retry::{delay::Fibonacci, retry, OperationResult};
 use serde::{Deserialize, Serialize};
 use serde_json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Deserialize, Debug, Clone, PartialEq, Hash, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -13,13 +16,49 @@ pub struct Package {
     id: String,
     pub list: UadList,
     pub description: String,
+    #[serde(default)]
+    descriptions: BTreeMap<String, String>, // translations of the description by language code
     dependencies: Vec<String>,
     needed_by: Vec<String>,
     labels: Vec<String>,
     pub removal: Removal,
+    #[serde(skip)]
+    pub source: Option<String>, // name of the last source overriding this entry
 }
 
-#[derive(Default, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+impl Package {
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Description in the selected language, or in English if it has not been translated yet
+    pub fn localized_description(&self) -> &str {
+        self.descriptions
+            .get(current_language().code)
+            .filter(|d| !d.trim().is_empty())
+            .unwrap_or(&self.description)
+    }
+}
+
+/// Additional list layered on top of the official list.
+///
+/// Its entries only need an `id` and the fields they override.
+#[derive(Default, Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ListSource {
+    pub name: String,
+    pub location: String, // URL or path of a JSON file
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct PackageOverride {
+    id: String,
+    list: Option<UadList>,
+    description: Option<String>,
+    removal: Option<Removal>,
+}
+
+#[derive(Default, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum UadList {
     #[default]
     All,
@@ -38,15 +77,34 @@ pub enum UadListState {
     Downloading,
     Done,
     Failed,
+    Rejected(SignatureError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureError {
+    Missing, // no signature published alongside the list
+    Invalid, // the list or its signature doesn't match the embedded public key
+}
+
+impl std::fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing => write!(f, "the list is not signed"),
+            Self::Invalid => write!(f, "the signature of the list is invalid"),
+        }
+    }
 }
 
 impl std::fmt::Display for UadListState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let date = last_modified_date(CACHE_DIR.join("uad_lists.json"));
         let s = match self {
-            Self::Downloading => "Checking updates...".to_string(),
-            Self::Done => format!("Done (last was {})", format_diff_time_from_now(date)),
-            Self::Failed => "Failed to check update!".to_string(),
+            Self::Downloading => tr("Checking updates..."),
+            Self::Done => {
+                tr("Done (last was {})").replacen("{}", &format_diff_time_from_now(date), 1)
+            }
+            Self::Failed => tr("Failed to check update!"),
+            Self::Rejected(e) => tr("Update rejected: {}!").replacen("{}", &tr(&e.to_string()), 1),
         };
         write!(f, "{s}")
     }
@@ -70,7 +128,7 @@ impl std::fmt::Display for UadList {
         write!(
             f,
             "{}",
-            match self {
+            tr(match self {
                 Self::All => "All lists",
                 Self::Aosp => "aosp",
                 Self::Carrier => "carrier",
@@ -79,7 +137,7 @@ impl std::fmt::Display for UadList {
                 Self::Oem => "oem",
                 Self::Pending => "pending",
                 Self::Unlisted => "unlisted",
-            }
+            })
         )
     }
 }
@@ -91,10 +149,17 @@ pub enum PackageState {
     Enabled,
     Uninstalled,
     Disabled,
+    Suspended,
 }
 
 impl PackageState {
-    pub const ALL: [Self; 4] = [Self::All, Self::Enabled, Self::Uninstalled, Self::Disabled];
+    pub const ALL: [Self; 5] = [
+        Self::All,
+        Self::Enabled,
+        Self::Uninstalled,
+        Self::Disabled,
+        Self::Suspended,
+    ];
 }
 
 impl std::fmt::Display for PackageState {
@@ -102,38 +167,69 @@ impl std::fmt::Display for PackageState {
         write!(
             f,
             "{}",
-            match self {
+            tr(match self {
                 Self::All => "All packages",
                 Self::Enabled => "Enabled",
                 Self::Uninstalled => "Uninstalled",
                 Self::Disabled => "Disabled",
-            }
+                Self::Suspended => "Suspended",
+            })
+        )
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NetworkState {
+    All,
+    #[default]
+    Allowed,
+    Blocked,
+}
+
+impl NetworkState {
+    pub const ALL: [Self; 3] = [Self::All, Self::Allowed, Self::Blocked];
+
+    pub const fn toggle(self) -> Self {
+        match self {
+            Self::Allowed => Self::Blocked,
+            Self::Blocked => Self::Allowed,
+            Self::All => Self::All,
+        }
+    }
+}
+
+impl std::fmt::Display for NetworkState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            tr(match self {
+                Self::All => "Any network",
+                Self::Allowed => "Network allowed",
+                Self::Blocked => "Network blocked",
+            })
         )
     }
 }
 
 pub trait Opposite {
-    fn opposite(&self, disable: bool) -> PackageState;
+    fn opposite(&self, removal_state: PackageState) -> PackageState;
 }
 
 impl Opposite for PackageState {
-    fn opposite(&self, disable: bool) -> Self {
+    // `removal_state` is the state chosen by the user to remove packages
+    // (see `DeviceSettings::removal_state()`)
+    fn opposite(&self, removal_state: PackageState) -> Self {
         match self {
-            Self::Enabled => {
-                if disable {
-                    Self::Disabled
-                } else {
-                    Self::Uninstalled
-                }
-            }
-            Self::Uninstalled | Self::Disabled => Self::Enabled,
+            Self::Enabled => removal_state,
+            Self::Uninstalled | Self::Disabled | Self::Suspended => Self::Enabled,
             Self::All => Self::All,
         }
     }
 }
 
 // Bad names. To be changed!
-#[derive(Default, Debug, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Default, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Removal {
     All,
     #[default]
@@ -160,47 +256,235 @@ impl std::fmt::Display for Removal {
         write!(
             f,
             "{}",
-            match self {
+            tr(match self {
                 Self::All => "All",
                 Self::Recommended => "Recommended",
                 Self::Advanced => "Advanced",
                 Self::Expert => "Expert",
                 Self::Unsafe => "Unsafe",
                 Self::Unlisted => "Unlisted",
-            }
+            })
         )
     }
 }
 
+/// Change of an entry of the official list between two updates
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ListChange {
+    Added(String, Removal),
+    Removed(String),
+    RemovalChanged(String, Removal, Removal), // (id, old, new)
+    DescriptionChanged(String),
+}
+
+impl ListChange {
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Added(id, _)
+            | Self::Removed(id)
+            | Self::RemovalChanged(id, _, _)
+            | Self::DescriptionChanged(id) => id,
+        }
+    }
+}
+
+impl std::fmt::Display for ListChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Added(_, removal) => {
+                write!(
+                    f,
+                    "{}",
+                    tr("Added ({})").replacen("{}", &removal.to_string(), 1)
+                )
+            }
+            Self::Removed(_) => write!(f, "{}", tr("Removed")),
+            Self::RemovalChanged(_, old, new) => write!(f, "{old} -> {new}"),
+            Self::DescriptionChanged(_) => write!(f, "{}", tr("Description changed")),
+        }
+    }
+}
+
+pub fn diff_lists(old: &[Package], new: &[Package]) -> Vec<ListChange> {
+    let old: HashMap<&str, &Package> = old.iter().map(|p| (p.id.as_str(), p)).collect();
+    let new: HashMap<&str, &Package> = new.iter().map(|p| (p.id.as_str(), p)).collect();
+    let mut changes = vec![];
+
+    for (id, p) in &new {
+        match old.get(id) {
+            None => changes.push(ListChange::Added(id.to_string(), p.removal)),
+            Some(old_p) => {
+                if old_p.removal != p.removal {
+                    changes.push(ListChange::RemovalChanged(
+                        id.to_string(),
+                        old_p.removal,
+                        p.removal,
+                    ));
+                }
+                if old_p.description != p.description {
+                    changes.push(ListChange::DescriptionChanged(id.to_string()));
+                }
+            }
+        }
+    }
+    for id in old.keys().filter(|id| !new.contains_key(*id)) {
+        changes.push(ListChange::Removed(id.to_string()));
+    }
+    changes.sort_by(|a, b| a.id().cmp(b.id()));
+    changes
+}
+
+/// Changes brought by the last update of the official list
+pub fn load_list_changes() -> Vec<ListChange> {
+    fs::read_to_string(CACHE_DIR.join("uad_lists_changes.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListError {
+    Io(String),
+    Parse(String),
+    Empty,
+}
+
+impl std::fmt::Display for ListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "unable to read the list: {e}"),
+            Self::Parse(e) => write!(f, "invalid list: {e}"),
+            Self::Empty => write!(f, "empty list"),
+        }
+    }
+}
+
+/// Parses a debloat list and checks that it is usable.
+pub fn parse_list(text: &str) -> Result<Vec<Package>, ListError> {
+    let list: Vec<Package> =
+        serde_json::from_str(text).map_err(|e| ListError::Parse(e.to_string()))?;
+    if list.is_empty() {
+        return Err(ListError::Empty);
+    }
+    if let Some(p) = list.iter().find(|p| p.id.trim().is_empty()) {
+        return Err(ListError::Parse(format!(
+            "entry without package name (list: {}, removal: {})",
+            p.list, p.removal
+        )));
+    }
+    Ok(list)
+}
+
 type PackageHashMap = HashMap<String, Package>;
-pub fn load_debloat_lists(remote: bool) -> (Result<PackageHashMap, PackageHashMap>, bool) {
+
+const UAD_LISTS_URL: &str = "https://raw.githubusercontent.com/0x192/universal-android-debloater/\
+    main/resources/assets/uad_lists.json";
+
+// HTTP validators of the cached list, sent back to only download the list when it changed
+#[derive(Default, Debug, Serialize, Deserialize)]
+struct CacheValidators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+struct RemoteList {
+    text: String,
+    signature: Option<String>,
+    validators: CacheValidators,
+}
+
+/// Loads the debloat lists and the configured sources layered on top of them.
+///
+/// The remote list is only used (and cached) if it has been signed with the key embedded
+/// in UAD. Otherwise, the cached or embedded list is returned along with the reason why
+/// the remote list couldn't be used.
+///
+/// A corrupted cached list is quarantined and replaced by the embedded list. The error
+/// is returned so that it can be reported to the user.
+pub fn load_debloat_lists(
+    remote: bool,
+    sources: &[ListSource],
+) -> (PackageHashMap, UadListState, Option<ListError>) {
     let cached_uad_lists: PathBuf = CACHE_DIR.join("uad_lists.json");
-    let mut error = false;
+    let cached_validators: PathBuf = CACHE_DIR.join("uad_lists_validators.json");
+    let mut state = UadListState::Done;
+
+    // Checked before anything else so that a corrupted list is downloaded again
+    let (cached_list, cache_error) = get_local_lists();
+
+    // Validators are useless without the list they belong to
+    let validators: CacheValidators = if cached_uad_lists.exists() {
+        fs::read_to_string(&cached_validators)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    } else {
+        CacheValidators::default()
+    };
+
+    // ~25s in total before giving up
+    let delays = Fibonacci::from_millis(500)
+        .map(|d| d.min(Duration::from_secs(8)))
+        .take(8);
+
     let list: Vec<Package> = if remote {
-        retry(Fixed::from_millis(1000).take(60), || {
-            match ureq::get(
-                "https://raw.githubusercontent.com/0x192/universal-android-debloater/\
-           main/resources/assets/uad_lists.json",
-            )
-            .call()
+        retry(delays, || {
+            let remote_list = match fetch_remote_list(&validators) {
+                Ok(Some(remote_list)) => remote_list,
+                Ok(None) => {
+                    info!("Debloat list is up to date");
+                    return OperationResult::Ok(cached_list.clone());
+                }
+                Err(e) => {
+                    warn!("Could not load remote debloat list: {}", e);
+                    return OperationResult::Retry(UadListState::Failed);
+                }
+            };
+            match remote_list
+                .signature
+                .ok_or(SignatureError::Missing)
+                .and_then(|s| verify_signature(&remote_list.text, &s))
             {
-                Ok(data) => {
-                    let text = data.into_string().expect("response should be Ok type");
-                    fs::write(cached_uad_lists.clone(), &text).expect("Unable to write file");
-                    let list = serde_json::from_str(&text).expect("Unable to parse");
+                Ok(()) => {
+                    // A signed list can still be broken. Downloading it again won't help
+                    let list = match parse_list(&remote_list.text) {
+                        Ok(list) => list,
+                        Err(e) => {
+                            error!("Remote debloat list is not valid: {}", e);
+                            return OperationResult::Err(UadListState::Failed);
+                        }
+                    };
+                    let changes = diff_lists(&cached_list, &list);
+                    info!("{} change(s) in the debloat list", changes.len());
+                    if let Err(e) = fs::write(
+                        CACHE_DIR.join("uad_lists_changes.json"),
+                        serde_json::to_string(&changes).unwrap_or_default(),
+                    ) {
+                        warn!("Could not save the changes of the debloat list: {}", e);
+                    }
+                    if let Err(e) = fs::write(&cached_uad_lists, &remote_list.text) {
+                        warn!("Could not cache the debloat list: {}", e);
+                    } else if let Err(e) = fs::write(
+                        &cached_validators,
+                        serde_json::to_string(&remote_list.validators).unwrap_or_default(),
+                    ) {
+                        warn!("Could not cache the validators of the debloat list: {}", e);
+                    }
                     OperationResult::Ok(list)
                 }
                 Err(e) => {
-                    warn!("Could not load remote debloat list: {}", e);
-                    error = true;
-                    OperationResult::Retry(Vec::<Package>::new())
+                    error!("Remote debloat list rejected: {}", e);
+                    OperationResult::Err(UadListState::Rejected(e))
                 }
             }
         })
-        .map_or_else(|_| get_local_lists(), |list| list)
+        .unwrap_or_else(|e| {
+            state = e.error;
+            cached_list.clone()
+        })
     } else {
         warn!("Could not load remote debloat list");
-        get_local_lists()
+        cached_list
     };
 
     // TODO: Do it without intermediary Vec?
@@ -209,23 +493,162 @@ pub fn load_debloat_lists(remote: bool) -> (Result<PackageHashMap, PackageHashMa
         let name = p.id.clone();
         package_lists.insert(name, p);
     }
-    if error {
-        (Err(package_lists), remote)
+    // Sources are applied in order: the last one has the highest precedence
+    for source in sources {
+        match load_list_source(source, remote) {
+            Ok(overrides) => apply_overrides(&mut package_lists, &source.name, overrides),
+            Err(e) => warn!("Could not load list source `{}`: {}", source.name, e),
+        }
+    }
+    (package_lists, state, cache_error)
+}
+
+// Returns `None` if the remote list didn't change since it was cached
+fn fetch_remote_list(validators: &CacheValidators) -> Result<Option<RemoteList>, ureq::Error> {
+    let mut request = ureq::get(UAD_LISTS_URL);
+    if let Some(etag) = &validators.etag {
+        request = request.set("If-None-Match", etag);
+    }
+    if let Some(last_modified) = &validators.last_modified {
+        request = request.set("If-Modified-Since", last_modified);
+    }
+    let response = request.call()?;
+    if response.status() == 304 {
+        return Ok(None);
+    }
+    let validators = CacheValidators {
+        etag: response.header("ETag").map(ToString::to_string),
+        last_modified: response.header("Last-Modified").map(ToString::to_string),
+    };
+    // An interrupted download is retried like a failed request
+    let text = response.into_string()?;
+
+    let signature = match ureq::get(&format!("{UAD_LISTS_URL}.minisig")).call() {
+        Ok(data) => Some(data.into_string()?),
+        Err(ureq::Error::Status(404, _)) => None,
+        Err(e) => return Err(e),
+    };
+    Ok(Some(RemoteList {
+        text,
+        signature,
+        validators,
+    }))
+}
+
+fn verify_signature(list: &str, signature: &str) -> Result<(), SignatureError> {
+    const PUBLIC_KEY: &str = include_str!("../../resources/assets/uad_lists.pub");
+    let public_key = PublicKey::decode(PUBLIC_KEY).expect("Invalid embedded public key");
+    let signature = Signature::decode(signature).map_err(|_| SignatureError::Invalid)?;
+    public_key
+        .verify(list.as_bytes(), &signature, false)
+        .map_err(|_| SignatureError::Invalid)
+}
+
+fn load_list_source(source: &ListSource, remote: bool) -> Result<Vec<PackageOverride>, String> {
+    let location = source.location.trim();
+    let text = if location.starts_with("https://") || location.starts_with("http://") {
+        // Keep a copy of remote sources to still be able to use them offline
+        let cached_source = CACHE_DIR.join(format!(
+            "source_{}.json",
+            source
+                .name
+                .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+        ));
+        match remote.then(|| ureq::get(location).call()) {
+            Some(Ok(data)) => {
+                let text = data.into_string().map_err(|e| e.to_string())?;
+                if let Err(e) = fs::write(&cached_source, &text) {
+                    warn!("Could not cache list source `{}`: {}", source.name, e);
+                }
+                text
+            }
+            res => {
+                if let Some(Err(e)) = res {
+                    warn!("Could not download list source `{}`: {}", source.name, e);
+                }
+                fs::read_to_string(&cached_source).map_err(|e| e.to_string())?
+            }
+        }
     } else {
-        (Ok(package_lists), remote)
+        fs::read_to_string(location).map_err(|e| e.to_string())?
+    };
+    serde_json::from_str(&text).map_err(|e| e.to_string())
+}
+
+fn apply_overrides(lists: &mut PackageHashMap, source: &str, overrides: Vec<PackageOverride>) {
+    for o in overrides {
+        if let Some(p) = lists.get_mut(&o.id) {
+            if let Some(list) = o.list {
+                p.list = list;
+            }
+            if let Some(description) = o.description {
+                // The translations don't match the new description anymore
+                p.descriptions.clear();
+                p.description = description;
+            }
+            if let Some(removal) = o.removal {
+                p.removal = removal;
+            }
+            p.source = Some(source.to_string());
+        } else if let (Some(list), Some(removal)) = (o.list, o.removal) {
+            lists.insert(
+                o.id.clone(),
+                Package {
+                    id: o.id,
+                    list,
+                    description: o.description.unwrap_or_default(),
+                    descriptions: BTreeMap::new(),
+                    dependencies: vec![],
+                    needed_by: vec![],
+                    labels: vec![],
+                    removal,
+                    source: Some(source.to_string()),
+                },
+            );
+        } else {
+            warn!(
+                "[{}] New entry `{}` ignored: `list` and `removal` are required",
+                source, o.id
+            );
+        }
     }
 }
 
-fn get_local_lists() -> Vec<Package> {
+// Returns the cached list or the embedded one if there is no valid cached list
+fn get_local_lists() -> (Vec<Package>, Option<ListError>) {
     const DATA: &str = include_str!("../../resources/assets/uad_lists.json");
     let cached_uad_lists = CACHE_DIR.join("uad_lists.json");
+    let embedded_list = || parse_list(DATA).expect("The embedded list should be valid");
 
-    if Path::new(&cached_uad_lists).exists() {
-        let data = fs::read_to_string(cached_uad_lists).unwrap();
-        serde_json::from_str(&data).expect("Unable to parse")
-    } else {
-        serde_json::from_str(DATA).expect("Unable to parse")
+    if !Path::new(&cached_uad_lists).exists() {
+        return (embedded_list(), None);
+    }
+    match fs::read_to_string(&cached_uad_lists)
+        .map_err(|e| ListError::Io(e.to_string()))
+        .and_then(|data| parse_list(&data))
+    {
+        Ok(list) => (list, None),
+        Err(e) => {
+            error!(
+                "Corrupted cached debloat list ({}). Fallback to the embedded list",
+                e
+            );
+            quarantine_cached_list();
+            (embedded_list(), Some(e))
+        }
+    }
+}
+
+// Keeps the corrupted list for investigation, out of the way of the next downloads
+fn quarantine_cached_list() {
+    let cached_uad_lists = CACHE_DIR.join("uad_lists.json");
+    let quarantined = CACHE_DIR.join("uad_lists.json.corrupted");
+    if let Err(e) = fs::rename(&cached_uad_lists, &quarantined) {
+        error!("Unable to quarantine the cached debloat list: {}", e);
+        let _ = fs::remove_file(&cached_uad_lists);
     }
+    // The validators would prevent downloading the list again
+    let _ = fs::remove_file(CACHE_DIR.join("uad_lists_validators.json"));
 }
 
 #[cfg(test)]
@@ -236,4 +659,99 @@ mod tests {
         const DATA: &str = include_str!("../../resources/assets/uad_lists.json");
         let _: Vec<Package> = serde_json::from_str(DATA).expect("Unable to parse");
     }
+
+    #[test]
+    fn test_parse_list() {
+        const DATA: &str = include_str!("../../resources/assets/uad_lists.json");
+        assert!(parse_list(DATA).is_ok());
+        assert_eq!(parse_list("[]"), Err(ListError::Empty));
+        assert!(matches!(
+            parse_list(&DATA[..DATA.len() / 2]),
+            Err(ListError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        const DATA: &str = include_str!("../../resources/assets/uad_lists.json");
+        const SIGNATURE: &str = include_str!("../../resources/assets/uad_lists.json.minisig");
+        assert_eq!(verify_signature(DATA, SIGNATURE), Ok(()));
+        let tampered = DATA.replacen("Recommended", "Unsafe", 1);
+        assert_eq!(
+            verify_signature(&tampered, SIGNATURE),
+            Err(SignatureError::Invalid)
+        );
+        assert_eq!(
+            verify_signature(DATA, "not a signature"),
+            Err(SignatureError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_diff_lists() {
+        let package = |id: &str, removal, description: &str| Package {
+            id: id.to_string(),
+            list: UadList::Oem,
+            description: description.to_string(),
+            descriptions: BTreeMap::new(),
+            dependencies: vec![],
+            needed_by: vec![],
+            labels: vec![],
+            removal,
+            source: None,
+        };
+        let old = [
+            package("com.a", Removal::Recommended, "A"),
+            package("com.b", Removal::Advanced, "B"),
+            package("com.c", Removal::Expert, "C"),
+        ];
+        let new = [
+            package("com.a", Removal::Unsafe, "A"),
+            package("com.b", Removal::Advanced, "B2"),
+            package("com.d", Removal::Recommended, "D"),
+        ];
+        assert_eq!(
+            diff_lists(&old, &new),
+            vec![
+                ListChange::RemovalChanged(
+                    "com.a".to_string(),
+                    Removal::Recommended,
+                    Removal::Unsafe
+                ),
+                ListChange::DescriptionChanged("com.b".to_string()),
+                ListChange::Removed("com.c".to_string()),
+                ListChange::Added("com.d".to_string(), Removal::Recommended),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        const DATA: &str = r#"[
+            {"id": "com.foo", "removal": "Expert"},
+            {"id": "com.bar", "list": "Oem", "removal": "Advanced", "description": "Bar"},
+            {"id": "com.baz", "description": "Missing list and removal"}
+        ]"#;
+        let base = Package {
+            id: "com.foo".to_string(),
+            list: UadList::Google,
+            description: "Foo".to_string(),
+            descriptions: BTreeMap::from([("fr".to_string(), "Truc".to_string())]),
+            dependencies: vec![],
+            needed_by: vec![],
+            labels: vec![],
+            removal: Removal::Recommended,
+            source: None,
+        };
+        let mut lists = HashMap::from([(base.id.clone(), base)]);
+        apply_overrides(&mut lists, "team", serde_json::from_str(DATA).unwrap());
+
+        assert_eq!(lists.len(), 2);
+        assert_eq!(lists["com.foo"].removal, Removal::Expert);
+        assert_eq!(lists["com.foo"].list, UadList::Google);
+        assert_eq!(lists["com.foo"].description, "Foo");
+        assert_eq!(lists["com.foo"].descriptions.len(), 1);
+        assert_eq!(lists["com.foo"].source.as_deref(), Some("team"));
+        assert_eq!(lists["com.bar"].list, UadList::Oem);
+    }
 }
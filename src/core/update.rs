@@ -1,3 +1,4 @@
+use crate::core::i18n::tr;
 use serde::Deserialize;
 
 #[cfg(feature = "self-update")]
@@ -46,7 +47,7 @@ impl std::fmt::Display for SelfUpdateStatus {
             Self::Failed => "Failed to check update!",
             Self::Done => "Done",
         };
-        write!(f, "{s}")
+        write!(f, "{}", tr(s))
     }
 }
 
@@ -0,0 +1,124 @@
+use crate::core::i18n::tr;
+use crate::core::sync::{adb_shell_command, AdbError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NewUserKind {
+    #[default]
+    Secondary,
+    Guest,
+    Ephemeral, // removed by Android as soon as it's stopped
+}
+
+impl NewUserKind {
+    pub const ALL: [Self; 3] = [Self::Secondary, Self::Guest, Self::Ephemeral];
+}
+
+impl std::fmt::Display for NewUserKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            tr(match self {
+                Self::Secondary => "Secondary user",
+                Self::Guest => "Guest",
+                Self::Ephemeral => "Disposable user",
+            })
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserAction {
+    Start,
+    Stop,
+    Switch,
+    Remove,
+}
+
+impl UserAction {
+    fn command(self, user_id: u16) -> String {
+        match self {
+            Self::Start => format!("am start-user {user_id}"),
+            Self::Stop => format!("am stop-user -f {user_id}"),
+            Self::Switch => format!("am switch-user {user_id}"),
+            Self::Remove => format!("pm remove-user {user_id}"),
+        }
+    }
+}
+
+fn create_user_command(name: &str, kind: NewUserKind) -> String {
+    // The name goes through the shell of the device
+    let name: String = name.chars().filter(|&c| c != '\'').collect();
+    let flag = match kind {
+        NewUserKind::Secondary => "",
+        NewUserKind::Guest => "--guest ",
+        NewUserKind::Ephemeral => "--ephemeral ",
+    };
+    format!("pm create-user {flag}'{}'", name.trim())
+}
+
+// `Success: created user id 11`
+fn parse_created_user(output: &str) -> Option<u16> {
+    output
+        .trim()
+        .strip_prefix("Success: created user id ")?
+        .parse()
+        .ok()
+}
+
+fn run(command: &str) -> Result<String, AdbError> {
+    match adb_shell_command(true, command) {
+        // `pm` and `am` don't always exit with an error code
+        Ok(o)
+            if ["Error", "Failure", "Exception"]
+                .iter()
+                .any(|e| o.contains(e)) =>
+        {
+            error!("[USERS] {} -> {}", command, o);
+            Err(AdbError::from_output(&o, Some(0)))
+        }
+        Ok(o) => {
+            info!("[USERS] {} -> {}", command, o);
+            Ok(o)
+        }
+        Err(err) => {
+            error!("[USERS] {} -> {}", command, err);
+            Err(err)
+        }
+    }
+}
+
+/// Creates a user and starts it in the background so that its packages can be changed.
+/// Returns the id of the new user.
+pub async fn create_user(name: String, kind: NewUserKind) -> Result<u16, AdbError> {
+    let output = run(&create_user_command(&name, kind))?;
+    let id = parse_created_user(&output).ok_or(AdbError::Failed(output))?;
+    run(&UserAction::Start.command(id))?;
+    Ok(id)
+}
+
+pub async fn manage_user(user_id: u16, action: UserAction) -> Result<(), AdbError> {
+    run(&action.command(user_id)).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_user() {
+        assert_eq!(
+            create_user_command(" Kid's phone ", NewUserKind::Ephemeral),
+            "pm create-user --ephemeral 'Kids phone'"
+        );
+        assert_eq!(
+            create_user_command("Test", NewUserKind::Secondary),
+            "pm create-user 'Test'"
+        );
+        assert_eq!(
+            parse_created_user("Success: created user id 11\n"),
+            Some(11)
+        );
+        assert_eq!(parse_created_user("Error: couldn't create User."), None);
+    }
+}
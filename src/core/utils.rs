@@ -1,6 +1,11 @@
-use crate::core::sync::{hashset_system_packages, list_all_system_packages, User};
+use crate::core::i18n::tr;
+use crate::core::safety::is_boot_critical;
+use crate::core::sync::{
+    hashmap_packages_dump, hashset_network_blocked_packages, hashset_system_packages,
+    list_all_system_packages, package_state, User,
+};
 use crate::core::theme::Theme;
-use crate::core::uad_lists::{Package, PackageState, Removal, UadList};
+use crate::core::uad_lists::{NetworkState, Package, PackageState, Removal, UadList};
 use crate::gui::widgets::package_row::PackageRow;
 use chrono::offset::Utc;
 use chrono::DateTime;
@@ -12,39 +17,71 @@ use std::{fmt, fs};
 pub fn fetch_packages(
     uad_lists: &HashMap<String, Package>,
     user_id: Option<&User>,
+    android_sdk: u8,
 ) -> Vec<PackageRow> {
     let all_system_packages = list_all_system_packages(user_id); // installed and uninstalled packages
     let enabled_system_packages = hashset_system_packages(PackageState::Enabled, user_id);
     let disabled_system_packages = hashset_system_packages(PackageState::Disabled, user_id);
+    let network_blocked_packages = hashset_network_blocked_packages(user_id, android_sdk);
+    let mut packages_dump = hashmap_packages_dump();
+    let no_description = tr("[No description] : CONTRIBUTION WELCOMED");
     let mut description;
     let mut uad_list;
     let mut state;
     let mut removal;
+    let mut network;
     let mut user_package: Vec<PackageRow> = Vec::new();
 
     for p_name in all_system_packages.lines() {
-        state = PackageState::Uninstalled;
-        description = "[No description] : CONTRIBUTION WELCOMED";
+        description = no_description.as_str();
         uad_list = UadList::Unlisted;
         removal = Removal::Unlisted;
+        network = NetworkState::Allowed;
 
         if uad_lists.contains_key(p_name) {
-            description = &uad_lists.get(p_name).unwrap().description;
+            description = uad_lists.get(p_name).unwrap().localized_description();
             if description.is_empty() {
-                description = "[No description] : CONTRIBUTION WELCOMED";
+                description = no_description.as_str();
             };
             uad_list = uad_lists.get(p_name).unwrap().list;
             removal = uad_lists.get(p_name).unwrap().removal;
         }
 
-        if enabled_system_packages.contains(p_name) {
-            state = PackageState::Enabled;
-        } else if disabled_system_packages.contains(p_name) {
-            state = PackageState::Disabled;
+        let mut dump = packages_dump.remove(p_name).unwrap_or_default();
+        let user_dump = dump
+            .users
+            .remove(&user_id.map_or(0, |u| u.id))
+            .unwrap_or_default();
+
+        state = package_state(
+            p_name,
+            &enabled_system_packages,
+            &disabled_system_packages,
+            user_dump.suspended,
+        );
+
+        if network_blocked_packages.contains(p_name) {
+            network = NetworkState::Blocked;
         }
 
-        let package_row =
-            PackageRow::new(p_name, state, description, uad_list, removal, false, false);
+        let mut package_row = PackageRow::new(
+            p_name,
+            state,
+            network,
+            description,
+            uad_list,
+            removal,
+            false,
+            false,
+        );
+        package_row.disabled_components = user_dump.disabled_components;
+        package_row.apk_name = dump.apk_name().to_string();
+        package_row.code_path.clone_from(&dump.code_path);
+        package_row.boot_critical = is_boot_critical(p_name, uad_lists.get(p_name));
+        package_row.source = uad_lists.get(p_name).and_then(|p| p.source.clone());
+        if dump.updated_system_app {
+            package_row.update_path = Some(dump.code_path);
+        }
         user_package.push(package_row);
     }
     user_package.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
@@ -96,17 +133,23 @@ pub fn last_modified_date(file: PathBuf) -> DateTime<Utc> {
     })
 }
 
+pub fn format_size(bytes: u64) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let mb = bytes as f64 / (1024.0 * 1024.0);
+    format!("{mb:.1} MB")
+}
+
 pub fn format_diff_time_from_now(date: DateTime<Utc>) -> String {
     let now: DateTime<Utc> = Utc::now();
     let last_update = now - date;
     if last_update.num_days() == 0 {
         if last_update.num_hours() == 0 {
-            last_update.num_minutes().to_string() + " min(s) ago"
+            tr("{} min(s) ago").replacen("{}", &last_update.num_minutes().to_string(), 1)
         } else {
-            last_update.num_hours().to_string() + " hour(s) ago"
+            tr("{} hour(s) ago").replacen("{}", &last_update.num_hours().to_string(), 1)
         }
     } else {
-        last_update.num_days().to_string() + " day(s) ago"
+        tr("{} day(s) ago").replacen("{}", &last_update.num_days().to_string(), 1)
     }
 }
 
@@ -2,6 +2,9 @@ pub mod style;
 pub mod views;
 pub mod widgets;
 
+use crate::core::i18n::set_language;
+use crate::core::queue::{run_job, JobKind, Queue};
+use crate::core::save::backup_packages_state;
 use crate::core::sync::{get_devices_list, perform_adb_commands, CommandType, Phone};
 use crate::core::theme::Theme;
 use crate::core::uad_lists::UadListState;
@@ -9,9 +12,11 @@ use crate::core::update::{get_latest_release, Release, SelfUpdateState, SelfUpda
 use crate::core::utils::string_to_theme;
 
 use views::about::{About as AboutView, Message as AboutMessage};
+use views::compare::{Compare as CompareView, Message as CompareMessage};
 use views::list::{List as AppsView, LoadingState as ListLoadingState, Message as AppsMessage};
-use views::settings::{Message as SettingsMessage, Settings as SettingsView};
+use views::settings::{Message as SettingsMessage, Settings as SettingsView, StatesSource};
 use widgets::navigation_menu::nav_menu;
+use widgets::queue_bar::queue_bar;
 
 use iced::widget::column;
 use iced::{
@@ -29,6 +34,7 @@ enum View {
     List,
     About,
     Settings,
+    Compare,
 }
 
 #[derive(Default, Clone)]
@@ -43,10 +49,12 @@ pub struct UadGui {
     apps_view: AppsView,
     about_view: AboutView,
     settings_view: SettingsView,
+    compare_view: CompareView,
     devices_list: Vec<Phone>,
     selected_device: Option<Phone>, // index of devices_list
     update_state: UpdateState,
-    nb_running_async_adb_commands: u32,
+    queue: Queue, // adb commands of the selected device
+    pending_user_states: Option<(StatesSource, u16)>, // (source, new user id)
 }
 
 #[derive(Debug, Clone)]
@@ -54,11 +62,16 @@ pub enum Message {
     // Navigation Panel
     AboutPressed,
     SettingsPressed,
+    ComparePressed,
     AppsPress,
     DeviceSelected(Phone),
     AboutAction(AboutMessage),
+    QueuePaused(bool),
+    QueueCancel,
+    QueueClear,
     AppsAction(AppsMessage),
     SettingsAction(SettingsMessage),
+    CompareAction(CompareMessage),
     RefreshButtonPressed,
     RebootButtonPressed,
     LoadDevices(Vec<Phone>),
@@ -74,8 +87,10 @@ impl Application for UadGui {
     type Flags = ();
 
     fn new(_flags: ()) -> (Self, Command<Message>) {
+        let app = Self::default();
+        set_language(&app.settings_view.general.language);
         (
-            Self::default(),
+            app,
             Command::batch([
                 Command::perform(get_devices_list(), Message::LoadDevices),
                 Command::perform(
@@ -108,6 +123,13 @@ impl Application for UadGui {
                     None => devices_list.first().cloned(),
                 };
                 self.devices_list = devices_list;
+                // `get_devices_list` leaves the last listed device in `ANDROID_SERIAL`
+                if let Some(device) = &self.selected_device {
+                    env::set_var("ANDROID_SERIAL", &device.adb_id);
+                    self.queue.set_device(&device.adb_id);
+                } else {
+                    env::remove_var("ANDROID_SERIAL");
+                }
 
                 #[allow(unused_must_use)]
                 {
@@ -132,12 +154,54 @@ impl Application for UadGui {
                 self.view = View::Settings;
                 Command::none()
             }
+            Message::ComparePressed => {
+                self.view = View::Compare;
+                self.update(Message::CompareAction(CompareMessage::LoadSources))
+            }
+            Message::CompareAction(msg) => {
+                let selected_device = self.selected_device.clone().unwrap_or_default();
+                if let CompareMessage::MakeBLikeA = msg {
+                    let changes = self.compare_view.alignment(&selected_device);
+                    let Some(user_id) = self.compare_view.compared_user() else {
+                        return Command::none();
+                    };
+                    self.view = View::List;
+                    return self.update(Message::AppsAction(AppsMessage::ApplyStates(
+                        user_id, changes,
+                    )));
+                }
+                let i_owner = selected_device
+                    .user_list
+                    .iter()
+                    .find(|u| u.id == 0)
+                    .map_or(0, |u| u.index);
+                let packages = match self.apps_view.loading_state {
+                    ListLoadingState::Ready(_) => self
+                        .apps_view
+                        .phone_packages
+                        .get(i_owner)
+                        .map_or(&[][..], Vec::as_slice),
+                    _ => &[],
+                };
+                self.compare_view
+                    .update(
+                        &self.devices_list,
+                        &selected_device,
+                        packages,
+                        &self.settings_view.device,
+                        msg,
+                    )
+                    .map(Message::CompareAction)
+            }
             Message::RefreshButtonPressed => {
                 self.apps_view = AppsView::default();
+                // The compared states may not be the ones of the devices anymore
+                self.compare_view = CompareView::default();
                 Command::perform(get_devices_list(), Message::LoadDevices)
             }
             Message::RebootButtonPressed => {
                 self.apps_view = AppsView::default();
+                self.compare_view = CompareView::default();
                 self.selected_device = None;
                 self.devices_list = vec![];
                 Command::perform(
@@ -145,19 +209,80 @@ impl Application for UadGui {
                     |_| Message::Nothing,
                 )
             }
-            Message::AppsAction(msg) => self
-                .apps_view
-                .update(
-                    &mut self.settings_view,
-                    &mut self.selected_device.clone().unwrap_or_default(),
-                    &mut self.update_state.uad_list,
-                    msg,
-                )
-                .map(Message::AppsAction),
+            Message::AppsAction(msg) => {
+                if let AppsMessage::ChangePackageState(_, ref res) = msg {
+                    self.queue.job_done(res.is_ok());
+                }
+                let command = self
+                    .apps_view
+                    .update(
+                        &mut self.settings_view,
+                        &mut self.selected_device.clone().unwrap_or_default(),
+                        &mut self.update_state.uad_list,
+                        &mut self.queue,
+                        msg,
+                    )
+                    .map(Message::AppsAction);
+                // Packages of a new user are loaded: give them the states of the source
+                if let ListLoadingState::Ready(_) = self.apps_view.loading_state {
+                    if let Some((source, target)) = self.pending_user_states.take() {
+                        let apply = match source {
+                            StatesSource::User(user) => {
+                                Some(AppsMessage::CopyUserStates(user.id, target))
+                            }
+                            StatesSource::Backup(backup) => {
+                                match backup_packages_state(&backup.path) {
+                                    Ok(states) => Some(AppsMessage::ApplyStates(
+                                        target,
+                                        states.into_iter().collect(),
+                                    )),
+                                    Err(e) => {
+                                        error!("[USERS] Can't read {}: {}", backup, e);
+                                        None
+                                    }
+                                }
+                            }
+                            StatesSource::Recommendations => {
+                                Some(AppsMessage::ApplyRecommendations(target))
+                            }
+                        };
+                        if let Some(apply) = apply {
+                            return Command::batch([
+                                command,
+                                self.update(Message::AppsAction(apply)),
+                            ]);
+                        }
+                    }
+                }
+                Command::batch([command, self.run_queue()])
+            }
+            Message::QueuePaused(paused) => {
+                self.queue.paused = paused;
+                self.run_queue()
+            }
+            Message::QueueCancel => {
+                let cancelled = self.queue.cancel();
+                info!("[QUEUE] {} job(s) cancelled", cancelled.len());
+                let nb = cancelled
+                    .iter()
+                    .filter(|job| job.kind == JobKind::PackageState)
+                    .count();
+                let command = self.update(Message::AppsAction(AppsMessage::JobsCancelled(nb)));
+                // The restore may have been cancelled before its last job
+                if self.queue.is_idle() && cancelled.iter().any(|job| job.kind == JobKind::Restore)
+                {
+                    return self.update(Message::RefreshButtonPressed);
+                }
+                command
+            }
+            Message::QueueClear => {
+                self.queue.clear();
+                Command::none()
+            }
             Message::SettingsAction(msg) => {
                 match msg {
                     SettingsMessage::RestoringDevice(ref output) => {
-                        self.nb_running_async_adb_commands -= 1;
+                        self.queue.job_done(output.is_ok());
                         self.view = View::List;
 
                         #[allow(unused_must_use)]
@@ -166,10 +291,11 @@ impl Application for UadGui {
                                 &mut self.settings_view,
                                 &mut self.selected_device.clone().unwrap_or_default(),
                                 &mut self.update_state.uad_list,
+                                &mut self.queue,
                                 AppsMessage::RestoringDevice(output.clone()),
                             );
                         }
-                        if self.nb_running_async_adb_commands == 0 {
+                        if self.queue.is_idle() {
                             return self.update(Message::RefreshButtonPressed);
                         }
                     }
@@ -193,16 +319,43 @@ impl Application for UadGui {
                             }
                         }
                     }
+                    SettingsMessage::UserCreated(Ok(id)) => {
+                        let device = self.selected_device.clone().unwrap_or_default();
+                        self.pending_user_states = self
+                            .settings_view
+                            .new_user_states_source(&device)
+                            .map(|source| (source, id));
+                        #[allow(unused_must_use)]
+                        {
+                            self.settings_view
+                                .update(&device, &[], &mut self.queue, msg);
+                        }
+                        return self.update(Message::RefreshButtonPressed);
+                    }
+                    SettingsMessage::UserActionDone(Ok(())) => {
+                        #[allow(unused_must_use)]
+                        {
+                            self.settings_view.update(
+                                &self.selected_device.clone().unwrap_or_default(),
+                                &[],
+                                &mut self.queue,
+                                msg,
+                            );
+                        }
+                        return self.update(Message::RefreshButtonPressed);
+                    }
                     _ => (),
                 }
-                self.settings_view
+                let command = self
+                    .settings_view
                     .update(
                         &self.selected_device.clone().unwrap_or_default(),
                         &self.apps_view.phone_packages,
-                        &mut self.nb_running_async_adb_commands,
+                        &mut self.queue,
                         msg,
                     )
-                    .map(Message::SettingsAction)
+                    .map(Message::SettingsAction);
+                Command::batch([command, self.run_queue()])
             }
             Message::AboutAction(msg) => {
                 self.about_view.update(msg.clone());
@@ -244,7 +397,9 @@ impl Application for UadGui {
             Message::DeviceSelected(s_device) => {
                 self.selected_device = Some(s_device.clone());
                 self.view = View::List;
-                env::set_var("ANDROID_SERIAL", s_device.adb_id);
+                self.compare_view = CompareView::default();
+                env::set_var("ANDROID_SERIAL", &s_device.adb_id);
+                self.queue.set_device(&s_device.adb_id);
                 info!("{:-^65}", "-");
                 info!(
                     "ANDROID_SDK: {} | DEVICE: {}",
@@ -260,6 +415,7 @@ impl Application for UadGui {
                 self.update(Message::AppsAction(AppsMessage::LoadPhonePackages((
                     self.apps_view.uad_lists.clone(),
                     UadListState::Done,
+                    None,
                 ))))
             }
             Message::_NewReleaseDownloaded(res) => {
@@ -342,9 +498,21 @@ impl Application for UadGui {
                 .settings_view
                 .view(&selected_device)
                 .map(Message::SettingsAction),
+            View::Compare => self
+                .compare_view
+                .view(&selected_device)
+                .map(Message::CompareAction),
         };
 
-        column![navigation_container, main_container]
+        let mut content = column![navigation_container];
+        // The summary of a single action isn't worth a bar
+        let (_, nb_jobs) = self.queue.progress();
+        if !self.queue.is_idle() || nb_jobs > 1 {
+            content = content.push(queue_bar(&self.queue));
+        }
+
+        content
+            .push(main_container)
             .width(Length::Fill)
             .align_items(Alignment::Center)
             .into()
@@ -352,6 +520,29 @@ impl Application for UadGui {
 }
 
 impl UadGui {
+    // Starts the queued jobs allowed to run
+    fn run_queue(&mut self) -> Command<Message> {
+        Command::batch(
+            self.queue
+                .next_jobs()
+                .into_iter()
+                .map(|job| match job.kind {
+                    JobKind::PackageState => {
+                        let command_type = job.command_type.clone();
+                        Command::perform(run_job(job), move |res| {
+                            Message::AppsAction(AppsMessage::ChangePackageState(
+                                command_type,
+                                res.map(|_| ()),
+                            ))
+                        })
+                    }
+                    JobKind::Restore => Command::perform(run_job(job), |res| {
+                        Message::SettingsAction(SettingsMessage::RestoringDevice(res))
+                    }),
+                }),
+        )
+    }
+
     pub fn start() -> iced::Result {
         Self::run(Settings {
             window: Window {
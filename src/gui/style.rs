@@ -1,7 +1,7 @@
 use crate::core::theme::Theme;
 use iced::overlay::menu;
 use iced::widget::{
-    button, checkbox, container, pick_list, radio, rule, scrollable, text, text_input,
+    button, checkbox, container, pick_list, progress_bar, radio, rule, scrollable, text, text_input,
 };
 use iced::{application, Background, Color};
 
@@ -477,3 +477,23 @@ impl rule::StyleSheet for Theme {
         }
     }
 }
+
+#[derive(Default, Clone, Copy)]
+pub enum ProgressBar {
+    #[default]
+    Default,
+}
+
+impl progress_bar::StyleSheet for Theme {
+    type Style = ProgressBar;
+
+    fn appearance(&self, style: &Self::Style) -> progress_bar::Appearance {
+        match style {
+            ProgressBar::Default => progress_bar::Appearance {
+                background: Background::Color(self.palette().base.foreground),
+                bar: Background::Color(self.palette().normal.primary),
+                border_radius: 5.0,
+            },
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::core::i18n::tr;
 use crate::core::theme::Theme;
 use crate::core::utils::{last_modified_date, open_url};
 use crate::gui::{style, UpdateState};
@@ -27,10 +28,10 @@ impl About {
         // other events are handled by UadGui update()
     }
     pub fn view(&self, update_state: &UpdateState) -> Element<Message, Renderer<Theme>> {
-        let about_text = text(
+        let about_text = text(tr(
             "Universal Android Debloater (UAD) is a Free and Open-Source community project aiming at simplifying \
             the removal of pre-installed apps on any Android device.",
-        );
+        ));
 
         let descr_container = container(about_text)
             .width(Length::Fill)
@@ -38,34 +39,36 @@ impl About {
             .style(style::Container::Frame);
 
         let date = last_modified_date(CACHE_DIR.join("uad_lists.json"));
-        let uad_list_text = text(format!("Documentation: v{}", date.format("%Y%m%d"))).width(250);
+        let uad_list_text =
+            text(tr("Documentation: v{}").replacen("{}", &date.format("%Y%m%d").to_string(), 1))
+                .width(250);
         let last_update_text = text(update_state.uad_list.to_string());
-        let uad_lists_btn = button("Update")
+        let uad_lists_btn = button(text(tr("Update")))
             .on_press(Message::UpdateUadLists)
             .padding(5)
             .style(style::Button::Primary);
 
         #[cfg(feature = "self-update")]
-        let self_update_btn = button("Update")
+        let self_update_btn = button(text(tr("Update")))
             .on_press(Message::DoSelfUpdate)
             .padding(5)
             .style(style::Button::Primary);
 
         #[cfg(feature = "self-update")]
         let uad_version_text =
-            text(format!("UAD version: v{}", env!("CARGO_PKG_VERSION"))).width(250);
+            text(tr("UAD version: v{}").replacen("{}", env!("CARGO_PKG_VERSION"), 1)).width(250);
 
         #[cfg(feature = "self-update")]
         #[rustfmt::skip]
         let self_update_text = update_state.self_update.latest_release.as_ref().map_or_else(||
             if update_state.self_update.status == SelfUpdateStatus::Done {
-                "(No update available)".to_string()
+                tr("(No update available)")
             } else {
                 update_state.self_update.status.to_string()
             }, |r| if update_state.self_update.status == SelfUpdateStatus::Updating {
                 update_state.self_update.status.to_string()
             } else {
-                format!("(v{} available)", r.tag_name)
+                tr("(v{} available)").replacen("{}", &r.tag_name, 1)
             });
 
         #[cfg(feature = "self-update")]
@@ -98,26 +101,26 @@ impl About {
             .padding(10)
             .style(style::Container::Frame);
 
-        let website_btn = button("Github page")
+        let website_btn = button(text(tr("Github page")))
             .on_press(Message::UrlPressed(PathBuf::from(
                 "https://github.com/0x192/universal-android-debloater",
             )))
             .padding(5)
             .style(style::Button::Primary);
 
-        let issue_btn = button("Have an issue?")
+        let issue_btn = button(text(tr("Have an issue?")))
             .on_press(Message::UrlPressed(PathBuf::from(
                 "https://github.com/0x192/universal-android-debloater/issues",
             )))
             .padding(5)
             .style(style::Button::Primary);
 
-        let log_btn = button("Locate the logfiles")
+        let log_btn = button(text(tr("Locate the logfiles")))
             .on_press(Message::UrlPressed(CACHE_DIR.to_path_buf()))
             .padding(5)
             .style(style::Button::Primary);
 
-        let wiki_btn = button("Wiki")
+        let wiki_btn = button(text(tr("Wiki")))
             .on_press(Message::UrlPressed(PathBuf::from(
                 "https://github.com/0x192/universal-android-debloater/wiki",
             )))
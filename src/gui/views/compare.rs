@@ -0,0 +1,342 @@
+use crate::core::compare::{alignment, differs, States};
+use crate::core::config::DeviceSettings;
+use crate::core::i18n::tr;
+use crate::core::recommend::{get_device_profile, recommended_states};
+use crate::core::save::{backup_packages_state, list_all_backups};
+use crate::core::sync::{get_device_packages_state, Phone, User};
+use crate::core::theme::Theme;
+use crate::core::uad_lists::PackageState;
+use crate::core::utils::DisplayablePath;
+use crate::gui::style;
+use crate::gui::widgets::package_row::PackageRow;
+use iced::widget::{button, column, container, pick_list, row, scrollable, text, Column, Space};
+use iced::{Alignment, Command, Element, Length, Renderer};
+use std::collections::{BTreeSet, HashMap};
+
+/// Where the package states come from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Device(Phone, User),
+    Backup(DisplayablePath),
+    Recommendations(Phone), // the device once its recommended packages are removed
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Device(phone, user) => {
+                write!(f, "{} ({}) - {user}", phone.model, phone.adb_id)
+            }
+            Self::Backup(backup) => {
+                // Backups are stored by device id
+                let device = backup
+                    .path
+                    .parent()
+                    .and_then(|p| p.file_name())
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                write!(f, "{} {backup} ({device})", tr("Backup"))
+            }
+            Self::Recommendations(phone) => write!(
+                f,
+                "{} ({} {})",
+                tr("Recommended for this device"),
+                phone.model,
+                phone.adb_id
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    A,
+    B,
+}
+
+type PackagesState = Result<HashMap<String, PackageState>, String>;
+
+#[derive(Default, Debug, Clone)]
+pub struct Compare {
+    sources: Vec<Source>,
+    a: Option<Source>,
+    b: Option<Source>,
+    states_a: Option<PackagesState>, // None while loading
+    states_b: Option<PackagesState>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    LoadSources,
+    SourceSelected(Side, Source),
+    StatesLoaded(Side, Source, PackagesState),
+    // Trigger an action in mod.rs (Message::CompareAction(msg))
+    MakeBLikeA,
+}
+
+impl Compare {
+    /// `packages` are the packages of the owner of the selected device
+    pub fn update(
+        &mut self,
+        devices: &[Phone],
+        selected_device: &Phone,
+        packages: &[PackageRow],
+        settings: &DeviceSettings,
+        msg: Message,
+    ) -> Command<Message> {
+        match msg {
+            Message::LoadSources => {
+                self.sources = devices
+                    .iter()
+                    .flat_map(device_sources)
+                    .chain(list_all_backups().into_iter().map(Source::Backup))
+                    .collect();
+                // Recommendations are computed from the package list of the selected device
+                if !packages.is_empty() {
+                    self.sources
+                        .push(Source::Recommendations(selected_device.clone()));
+                }
+                if self.b.is_none() && !selected_device.adb_id.is_empty() {
+                    self.b = device_sources(selected_device).into_iter().next();
+                }
+                // States may have changed since the last comparison
+                let mut commands = vec![];
+                for (side, source) in [(Side::A, self.a.clone()), (Side::B, self.b.clone())] {
+                    if let Some(source) = source {
+                        commands.push(self.update(
+                            devices,
+                            selected_device,
+                            packages,
+                            settings,
+                            Message::SourceSelected(side, source),
+                        ));
+                    }
+                }
+                Command::batch(commands)
+            }
+            Message::SourceSelected(side, source) => {
+                match side {
+                    Side::A => {
+                        self.a = Some(source.clone());
+                        self.states_a = None;
+                    }
+                    Side::B => {
+                        self.b = Some(source.clone());
+                        self.states_b = None;
+                    }
+                }
+                let packages = match source {
+                    Source::Recommendations(_) => packages.to_vec(),
+                    _ => vec![],
+                };
+                Command::perform(
+                    load_states(
+                        source.clone(),
+                        packages,
+                        settings.removal_state(),
+                        settings.root_mode,
+                    ),
+                    move |states| Message::StatesLoaded(side, source, states),
+                )
+            }
+            Message::StatesLoaded(side, source, states) => {
+                // A result of a source that is not selected anymore
+                match side {
+                    Side::A if self.a.as_ref() == Some(&source) => self.states_a = Some(states),
+                    Side::B if self.b.as_ref() == Some(&source) => self.states_b = Some(states),
+                    _ => (),
+                }
+                Command::none()
+            }
+            Message::MakeBLikeA => Command::none(),
+        }
+    }
+
+    /// (package name, state) changes making B look like A. Empty if B is not the selected
+    /// device: UAD only changes the packages of the selected device.
+    pub fn alignment(&self, selected_device: &Phone) -> Vec<(String, PackageState)> {
+        match (&self.b, &self.states_a, &self.states_b) {
+            (Some(Source::Device(b, _)), Some(Ok(a_states)), Some(Ok(b_states)))
+                if b.adb_id == selected_device.adb_id =>
+            {
+                alignment(&borrow_states(a_states), &borrow_states(b_states))
+                    .into_iter()
+                    .map(|(name, state)| (name.to_string(), state))
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Id of the user of B whose packages are compared (when B is a device)
+    pub fn compared_user(&self) -> Option<u16> {
+        match &self.b {
+            Some(Source::Device(_, user)) => Some(user.id),
+            _ => None,
+        }
+    }
+
+    pub fn view(&self, selected_device: &Phone) -> Element<Message, Renderer<Theme>> {
+        let source_picklist = |side, selected: &Option<Source>| {
+            pick_list(self.sources.clone(), selected.clone(), move |source| {
+                Message::SourceSelected(side, source)
+            })
+            .placeholder(tr("Select a device, a backup or the recommendations"))
+            .width(Length::Fill)
+        };
+
+        let sources_row = row![
+            text("A"),
+            source_picklist(Side::A, &self.a),
+            text("B"),
+            source_picklist(Side::B, &self.b),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let content = match (&self.a, &self.states_a, &self.b, &self.states_b) {
+            (None, _, _, _) | (_, _, None, _) => {
+                column![text(tr("Select the two sources to compare"))]
+            }
+            (_, Some(Err(e)), _, _) | (_, _, _, Some(Err(e))) => {
+                column![text(e).style(style::Text::Danger)]
+            }
+            (_, Some(Ok(a_states)), _, Some(Ok(b_states))) => {
+                self.differences_view(selected_device, a_states, b_states)
+            }
+            _ => column![text(tr("Loading the package states..."))],
+        };
+
+        container(column![sources_row, content.height(Length::Fill)].spacing(20))
+            .padding(10)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn differences_view<'a>(
+        &self,
+        selected_device: &Phone,
+        a_states: &'a HashMap<String, PackageState>,
+        b_states: &'a HashMap<String, PackageState>,
+    ) -> Column<'a, Message, Renderer<Theme>> {
+        let sources = [borrow_states(a_states), borrow_states(b_states)];
+        let names: BTreeSet<&str> = a_states
+            .keys()
+            .chain(b_states.keys())
+            .map(String::as_str)
+            .filter(|name| differs(name, &sources))
+            .collect();
+
+        let state_text = |state: Option<&PackageState>| {
+            text(state.map_or_else(|| "-".to_string(), ToString::to_string))
+                .width(Length::FillPortion(1))
+        };
+
+        let header = row![
+            text(tr("Package")).width(Length::FillPortion(3)),
+            text("A").width(Length::FillPortion(1)),
+            text("B").width(Length::FillPortion(1)),
+        ]
+        .spacing(10);
+
+        let rows = names.iter().fold(column![].spacing(6), |col, &name| {
+            col.push(
+                row![
+                    text(name).width(Length::FillPortion(3)),
+                    state_text(a_states.get(name)),
+                    state_text(b_states.get(name)).style(style::Text::Danger),
+                ]
+                .spacing(10),
+            )
+        });
+
+        let nb_changes = self.alignment(selected_device).len();
+        let mut make_b_like_a_btn = button(text(tr("Make B look like A ({} changes)").replacen(
+            "{}",
+            &nb_changes.to_string(),
+            1,
+        )))
+        .padding(5);
+        if nb_changes > 0 {
+            make_b_like_a_btn = make_b_like_a_btn
+                .on_press(Message::MakeBLikeA)
+                .style(style::Button::Primary);
+        }
+
+        let footer = row![
+            text(tr(
+                "B must be the selected device. Packages missing from B can't be installed by UAD."
+            ))
+            .style(style::Text::Commentary)
+            .size(15),
+            Space::new(Length::Fill, Length::Shrink),
+            make_b_like_a_btn,
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        column![
+            text(tr("{} packages differ").replacen("{}", &names.len().to_string(), 1)),
+            container(header).padding([0, 15, 0, 10]),
+            scrollable(container(rows).padding([0, 15, 0, 10]))
+                .height(Length::Fill)
+                .style(style::Scrollable::Packages),
+            footer,
+        ]
+        .spacing(10)
+    }
+}
+
+// One source per user of the device whose packages can be changed
+fn device_sources(phone: &Phone) -> Vec<Source> {
+    let users: Vec<User> = phone
+        .user_list
+        .iter()
+        .filter(|u| !u.protected)
+        .cloned()
+        .collect();
+    if users.is_empty() {
+        return vec![Source::Device(phone.clone(), User::default())];
+    }
+    users
+        .into_iter()
+        .map(|user| Source::Device(phone.clone(), user))
+        .collect()
+}
+
+fn borrow_states(states: &HashMap<String, PackageState>) -> States {
+    states
+        .iter()
+        .map(|(name, state)| (name.as_str(), *state))
+        .collect()
+}
+
+// `packages` of the owner are only needed for the recommendations
+async fn load_states(
+    source: Source,
+    packages: Vec<PackageRow>,
+    removal_state: PackageState,
+    root: bool,
+) -> PackagesState {
+    match source {
+        Source::Device(phone, user) => {
+            // `--user` is only supported since Android 5.0
+            let user = (phone.android_sdk >= 21).then_some(user);
+            let states = get_device_packages_state(phone.adb_id, user).await;
+            if states.is_empty() {
+                Err(tr("No package found: is the device still connected?"))
+            } else {
+                Ok(states)
+            }
+        }
+        Source::Backup(backup) => backup_packages_state(&backup.path),
+        Source::Recommendations(phone) => {
+            if packages.is_empty() {
+                return Err(tr("No package found: is the device still connected?"));
+            }
+            let profile = get_device_profile(phone.android_sdk).await;
+            Ok(recommended_states(&packages, &profile, removal_state, root))
+        }
+    }
+}
@@ -1,20 +1,39 @@
+use crate::core::compare::{alignment, differs, States};
 use crate::core::config::DeviceSettings;
-use crate::core::sync::{apply_pkg_state_commands, perform_adb_commands, CommandType, Phone, User};
+use crate::core::contribute::{
+    collect_unlisted_packages, export_contributions, Contribution, CONTRIBUTIONS_DIR,
+};
+use crate::core::dumpsys::{Component, PackageSize};
+use crate::core::i18n::tr;
+use crate::core::queue::{Job, JobKind, Queue};
+use crate::core::recommend::{get_device_profile, recommend, DeviceProfile, Recommendation};
+use crate::core::root::{overlay_remove_command, overlay_restore_command};
+use crate::core::safety::{is_boot_critical, reboot_and_wait, write_recovery_script, RECOVERY_DIR};
+use crate::core::search::parse_query;
+use crate::core::sync::{
+    apply_component_commands, apply_pkg_state_commands, apply_uninstall_updates_commands,
+    get_data_free_space, get_package_components, get_packages_size, get_packages_state,
+    perform_adb_commands, perform_network_commands, perform_uninstall_updates, AdbError,
+    CommandType, Phone, User,
+};
 use crate::core::theme::Theme;
 use crate::core::uad_lists::{
-    load_debloat_lists, Opposite, Package, PackageState, Removal, UadList, UadListState,
+    load_debloat_lists, load_list_changes, ListChange, ListError, ListSource, NetworkState,
+    Opposite, Package, PackageState, Removal, UadList, UadListState,
 };
-use crate::core::utils::fetch_packages;
+use crate::core::utils::{fetch_packages, format_size, open_url};
 use crate::gui::style;
 use crate::gui::widgets::navigation_menu::ICONS;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::path::PathBuf;
 
 use crate::gui::views::settings::Settings;
 use crate::gui::widgets::modal::Modal;
-use crate::gui::widgets::package_row::{Message as RowMessage, PackageRow};
+use crate::gui::widgets::package_row::{action_text_for, Message as RowMessage, PackageRow};
+use crate::CACHE_DIR;
 use iced::widget::{
-    button, column, container, horizontal_space, pick_list, radio, row, scrollable, text,
+    button, checkbox, column, container, horizontal_space, pick_list, radio, row, scrollable, text,
     text_input, tooltip, vertical_rule, Space,
 };
 use iced::{alignment, Alignment, Command, Element, Length, Renderer};
@@ -26,6 +45,29 @@ pub struct PackageInfo {
     pub removal: String,
 }
 
+// Packages whose state is being changed by a validated selection
+#[derive(Default, Debug, Clone)]
+struct Batch {
+    pending: usize,                      // nb of package state changes still running
+    sizes: HashMap<String, PackageSize>, // sizes measured before the batch
+    free_space: Option<u64>,             // free space of the data partition before the batch
+    changed: Vec<(usize, usize)>,        // (user_index, pkg_index) of changed packages
+    targets: Vec<(usize, usize, PackageState)>, // (user_index, pkg_index, expected state)
+    review: bool,                        // started from the review modal
+    errors: Vec<AdbError>,               // failed package state changes
+}
+
+#[derive(Default, Debug, Clone)]
+struct BatchReport {
+    nb_removed: usize,
+    freed: u64,
+    measured: bool, // `freed` is measured on the device (estimated from the sizes otherwise)
+    freed_by_removal: HashMap<Removal, u64>,
+    freed_by_list: HashMap<UadList, u64>,
+    mismatches: Vec<String>, // packages not in the expected state after the batch
+    errors: Vec<(String, usize)>, // (error message, nb of failed packages)
+}
+
 #[derive(Debug, Clone)]
 pub enum LoadingState {
     DownloadingList(String),
@@ -50,34 +92,107 @@ pub struct List {
     filtered_packages: Vec<usize>, // phone_packages indexes of the selected user (= what you see on screen)
     selected_packages: Vec<(usize, usize)>, // Vec of (user_index, pkg_index)
     selected_package_state: Option<PackageState>,
+    selected_network_state: Option<NetworkState>,
     selected_removal: Option<Removal>,
     selected_list: Option<UadList>,
+    failed_filter: bool, // only packages that failed in the last batch
     selected_user: Option<User>,
     pub input_value: String,
     description: String,
     selection_modal: bool,
+    components_modal: bool,
+    components: Option<Vec<Component>>, // components of the current package (None while loading)
     current_package_index: usize,
+    updates_recap: Option<(u32, u32, u64)>, // (nb of removed updates, of failures, reclaimed bytes)
+    packages_size: HashMap<String, PackageSize>,
+    free_space: Option<u64>, // free space of the data partition when the selection was reviewed
+    batch: Option<Batch>,
+    batch_report: Option<BatchReport>,
+    list_changes: Vec<ListChange>, // changes brought by the last update of the list
+    list_changes_modal: bool,
+    list_error: Option<ListError>, // error of the cached list replaced by the embedded one
+    list_refreshed: bool,          // the background refresh of the list is over
+    contribute_modal: bool,
+    contributions: Option<Vec<Contribution>>, // None while loading
+    contributions_export: Option<Result<PathBuf, String>>,
+    recommend_modal: bool,
+    device_profile: Option<DeviceProfile>, // None while loading
+    recommendations: Vec<Recommendation>,
+    critical_confirmation: String, // must match the device model to remove boot-critical packages
+    trial: Option<Trial>,
+    overlay_status: Option<String>, // result of the last Magisk module change
+    user_matrix: bool,              // states of the packages for all the users side by side
+    matrix_differs: bool,           // only packages not in the same state for all the users
+    matrix_reference: Option<User>, // user the others are aligned on
 }
 
+#[derive(Debug, Clone)]
+enum Trial {
+    Rebooting(PathBuf),      // recovery script
+    Booted(u64),             // boot duration in seconds
+    Failed(PathBuf, String), // recovery script, error
+}
+
+type ListBox = (HashMap<String, Package>, UadListState, Option<ListError>);
+
 #[derive(Debug, Clone)]
 pub enum Message {
     LoadUadList(bool),
-    LoadPhonePackages((HashMap<String, Package>, UadListState)),
-    RestoringDevice(Result<CommandType, ()>),
+    UadListRefreshed(ListBox),
+    ShowListChanges,
+    ShowContribute,
+    ContributionsLoaded(Vec<Contribution>),
+    ContributionDescription(usize, String),
+    ContributionList(usize, UadList),
+    ContributionRemoval(usize, Removal),
+    ExportContributions,
+    OpenContributionsDir,
+    Recommend,
+    DeviceProfileLoaded(DeviceProfile),
+    SelectRecommendations,
+    LoadPhonePackages(ListBox),
+    RestoringDevice(Result<CommandType, AdbError>),
     ApplyFilters(Vec<Vec<PackageRow>>),
     SearchInputChanged(String),
     ToggleAllSelected(bool),
     ListSelected(UadList),
     UserSelected(User),
     PackageStateSelected(PackageState),
+    NetworkStateSelected(NetworkState),
     RemovalSelected(Removal),
     ApplyActionOnSelection,
     List(usize, RowMessage),
-    ChangePackageState(Result<CommandType, ()>),
+    ChangePackageState(CommandType, Result<(), AdbError>),
+    // (.., network state read back from the device, result of the commands)
+    ChangeNetworkState(CommandType, Option<NetworkState>, Result<(), AdbError>),
     Nothing,
     ModalHide,
-    ModalUserSelected(User),
+    ModalUserSelected(usize),
     ModalValidate,
+    ShowComponents,
+    ComponentsLoaded(Vec<Component>),
+    ToggleComponent(String, bool),
+    ComponentChanged(String, bool, Result<CommandType, AdbError>), // (component, enabled, ..)
+    RevertToFactory,
+    StripSelectedUpdates,
+    SystemUpdateRemoved(CommandType, Result<u64, AdbError>), // (.., reclaimed bytes)
+    PackagesSizeLoaded(HashMap<String, PackageSize>, Option<u64>),
+    BatchVerified(Vec<(usize, HashMap<String, PackageState>)>, Option<u64>),
+    CriticalConfirmationChanged(String),
+    TrialFinished(Result<u64, String>),
+    OpenRecoveryDir,
+    JobsCancelled(usize), // nb of package state jobs dropped from the queue
+    FailedFilterToggled(bool),
+    OverlayPressed(bool), // remove the current package from its system partition (or put it back)
+    OverlayChanged(Result<CommandType, AdbError>),
+    CopyUserStates(u16, u16), // (source user id, target user id)
+    UserMatrixToggled(bool),
+    MatrixDiffersToggled(bool),
+    MatrixReferenceSelected(User),
+    AlignUsers,
+    ApplyStates(u16, Vec<(String, PackageState)>), // (user id, states of packages by name)
+    ApplyRecommendations(u16),                     // user id
+    UserProfileLoaded(u16, DeviceProfile),
 }
 
 impl List {
@@ -86,38 +201,102 @@ impl List {
         settings: &mut Settings,
         selected_device: &mut Phone,
         list_update_state: &mut UadListState,
+        queue: &mut Queue,
         message: Message,
     ) -> Command<Message> {
-        let i_user = self.selected_user.unwrap_or_default().index;
+        let i_user = self.selected_user.as_ref().map_or(0, |u| u.index);
         match message {
             Message::ModalHide => {
                 self.selection_modal = false;
+                self.components_modal = false;
+                self.list_changes_modal = false;
+                self.contribute_modal = false;
+                self.recommend_modal = false;
+                self.critical_confirmation.clear();
+                // The trial modal is displayed once the batch report is closed
+                if self.batch_report.take().is_none()
+                    && !matches!(self.trial, Some(Trial::Rebooting(_)))
+                {
+                    self.trial = None;
+                }
                 Command::none()
             }
             Message::ModalValidate => {
-                let mut commands = vec![];
+                if !self.boot_critical_selection().is_empty()
+                    && self.critical_confirmation.trim() != selected_device.model
+                {
+                    return Command::none();
+                }
+                self.critical_confirmation.clear();
+                self.clear_failures();
+                let mut jobs = vec![];
+                let mut batch = self.batch.take().unwrap_or_else(|| Batch {
+                    sizes: self.packages_size.clone(),
+                    free_space: self.free_space.take(),
+                    ..Batch::default()
+                });
+                batch.review = true;
                 self.selected_packages.sort_unstable();
                 self.selected_packages.dedup();
                 for selection in &self.selected_packages {
-                    commands.append(&mut build_action_pkg_commands(
+                    let (mut p_jobs, targets) = build_action_pkg_jobs(
                         &self.phone_packages,
                         selected_device,
                         &settings.device,
                         *selection,
-                    ));
+                    );
+                    jobs.append(&mut p_jobs);
+                    batch.pending += targets.len();
+                    batch.targets.extend(targets);
+                }
+                if batch.pending > 0 {
+                    self.batch = Some(batch);
                 }
                 self.selection_modal = false;
-                Command::batch(commands)
+                queue.push(jobs);
+                Command::none()
+            }
+            Message::CriticalConfirmationChanged(input) => {
+                self.critical_confirmation = input;
+                Command::none()
+            }
+            Message::TrialFinished(res) => {
+                if let Some(Trial::Rebooting(script)) = self.trial.take() {
+                    self.trial = Some(match res {
+                        Ok(duration) => Trial::Booted(duration),
+                        Err(e) => {
+                            error!("[TRIAL] {}", e);
+                            Trial::Failed(script, e)
+                        }
+                    });
+                }
+                Command::none()
+            }
+            Message::OpenRecoveryDir => {
+                open_url(RECOVERY_DIR.to_path_buf());
+                Command::none()
+            }
+            Message::PackagesSizeLoaded(sizes, free_space) => {
+                // Sizes are the ones of the daily job of Android: still valid if the selection
+                // was validated before they were loaded
+                if let Some(batch) = self.batch.as_mut().filter(|b| b.sizes.is_empty()) {
+                    batch.sizes.clone_from(&sizes);
+                }
+                self.packages_size = sizes;
+                self.free_space = free_space;
+                Command::none()
             }
             Message::RestoringDevice(output) => {
-                if let Ok(res) = output {
-                    if let CommandType::PackageManager(p) = res {
+                match output {
+                    Ok(CommandType::PackageManager(p)) => {
                         self.loading_state = LoadingState::RestoringDevice(
                             self.phone_packages[i_user][p.index].name.clone(),
                         );
                     }
-                } else {
-                    self.loading_state = LoadingState::RestoringDevice("Error [TODO]".to_string());
+                    Ok(CommandType::Shell) => (),
+                    Err(err) => {
+                        self.loading_state = LoadingState::RestoringDevice(err.to_string());
+                    }
                 }
                 Command::none()
             }
@@ -128,29 +307,189 @@ impl List {
                     selected_device.android_sdk, selected_device.model
                 );
                 info!("{:-^65}", "-");
+                if remote && settings.general.background_list_refresh {
+                    // Start with the cached list and check for updates in the background
+                    let sources = settings.general.list_sources.clone();
+                    self.loading_state = LoadingState::LoadingPackages(String::new());
+                    self.list_refreshed = false;
+                    return Command::batch([
+                        Command::perform(
+                            Self::init_apps_view(false, sources.clone(), selected_device.clone()),
+                            |(list, _, list_error)| {
+                                Message::LoadPhonePackages((
+                                    list,
+                                    UadListState::Downloading,
+                                    list_error,
+                                ))
+                            },
+                        ),
+                        Command::perform(
+                            async move { load_debloat_lists(true, &sources) },
+                            Message::UadListRefreshed,
+                        ),
+                    ]);
+                }
                 self.loading_state = LoadingState::DownloadingList(String::new());
                 Command::perform(
-                    Self::init_apps_view(remote, selected_device.clone()),
+                    Self::init_apps_view(
+                        remote,
+                        settings.general.list_sources.clone(),
+                        selected_device.clone(),
+                    ),
                     Message::LoadPhonePackages,
                 )
             }
+            Message::UadListRefreshed((uad_list, list_state, list_error)) => {
+                self.list_refreshed = true;
+                *list_update_state = list_state;
+                if list_error.is_some() {
+                    self.list_error = list_error;
+                }
+                if list_state == UadListState::Done {
+                    self.uad_lists = uad_list;
+                    self.update_package_descriptions();
+                    self.list_changes = load_list_changes();
+                    if !self.phone_packages.is_empty() {
+                        Self::filter_package_lists(self);
+                    }
+                }
+                Command::none()
+            }
+            Message::ShowListChanges => {
+                self.list_changes_modal = true;
+                Command::none()
+            }
+            Message::ShowContribute => {
+                let unlisted = self.phone_packages[i_user]
+                    .iter()
+                    .filter(|p| p.uad_list == UadList::Unlisted)
+                    .filter(|p| p.state != PackageState::Uninstalled)
+                    .map(|p| p.name.clone())
+                    .collect();
+                self.contribute_modal = true;
+                self.contributions = None;
+                self.contributions_export = None;
+                Command::perform(
+                    collect_unlisted_packages(unlisted),
+                    Message::ContributionsLoaded,
+                )
+            }
+            Message::ContributionsLoaded(contributions) => {
+                self.contributions = Some(contributions);
+                Command::none()
+            }
+            Message::ContributionDescription(i, description) => {
+                if let Some(c) = self.contributions.as_mut().and_then(|c| c.get_mut(i)) {
+                    c.description = description;
+                }
+                Command::none()
+            }
+            Message::ContributionList(i, list) => {
+                if let Some(c) = self.contributions.as_mut().and_then(|c| c.get_mut(i)) {
+                    c.list = list;
+                }
+                Command::none()
+            }
+            Message::ContributionRemoval(i, removal) => {
+                if let Some(c) = self.contributions.as_mut().and_then(|c| c.get_mut(i)) {
+                    c.removal = removal;
+                }
+                Command::none()
+            }
+            Message::ExportContributions => {
+                if let Some(contributions) = &self.contributions {
+                    self.contributions_export =
+                        Some(export_contributions(contributions, &selected_device.adb_id));
+                }
+                Command::none()
+            }
+            Message::Recommend => {
+                self.recommend_modal = true;
+                self.device_profile = None;
+                self.recommendations = vec![];
+                Command::perform(
+                    get_device_profile(selected_device.android_sdk),
+                    Message::DeviceProfileLoaded,
+                )
+            }
+            Message::DeviceProfileLoaded(profile) => {
+                self.recommendations = recommend(
+                    &self.phone_packages[i_user],
+                    &profile,
+                    settings.device.removal_state(),
+                    settings.device.root_mode,
+                );
+                self.device_profile = Some(profile);
+                Command::none()
+            }
+            Message::SelectRecommendations => {
+                #[allow(unused_must_use)]
+                for i in self
+                    .recommendations
+                    .iter()
+                    .map(|r| r.index)
+                    .collect::<Vec<_>>()
+                {
+                    if !self.phone_packages[i_user][i].selected {
+                        self.update(
+                            settings,
+                            selected_device,
+                            list_update_state,
+                            queue,
+                            Message::List(i, RowMessage::ToggleSelection(true)),
+                        );
+                    }
+                }
+                self.recommend_modal = false;
+                Command::none()
+            }
+            Message::OpenContributionsDir => {
+                open_url(CONTRIBUTIONS_DIR.to_path_buf());
+                Command::none()
+            }
             Message::LoadPhonePackages(list_box) => {
-                let (uad_list, list_state) = list_box;
+                let (mut uad_list, mut list_state, list_error) = list_box;
+                // The background refresh of the list was faster: keep its result
+                if list_state == UadListState::Downloading && self.list_refreshed {
+                    list_state = *list_update_state;
+                    if list_state == UadListState::Done {
+                        uad_list = self.uad_lists.clone();
+                    }
+                }
                 self.loading_state = LoadingState::LoadingPackages(String::new());
+                // Indexes of the running batch don't survive a reload
+                self.batch = None;
                 self.uad_lists = uad_list.clone();
+                if list_error.is_some() {
+                    self.list_error = list_error;
+                }
                 *list_update_state = list_state;
                 Command::perform(
-                    Self::load_packages(uad_list, selected_device.user_list.clone()),
+                    Self::load_packages(
+                        uad_list,
+                        selected_device.user_list.clone(),
+                        selected_device.android_sdk,
+                    ),
                     Message::ApplyFilters,
                 )
             }
             Message::ApplyFilters(packages) => {
                 self.phone_packages = packages;
+                // The list may have been refreshed while the packages were loading
+                self.update_package_descriptions();
                 self.filtered_packages = (0..self.phone_packages[i_user].len()).collect();
                 self.selected_package_state = Some(PackageState::Enabled);
+                self.selected_network_state = Some(NetworkState::All);
                 self.selected_removal = Some(Removal::Recommended);
                 self.selected_list = Some(UadList::All);
-                self.selected_user = Some(User::default());
+                self.selected_user = Some(
+                    selected_device
+                        .user_list
+                        .first()
+                        .cloned()
+                        .unwrap_or_default(),
+                );
+                self.list_changes = load_list_changes();
                 Self::filter_package_lists(self);
                 self.loading_state = LoadingState::Ready(String::new());
                 Command::none()
@@ -163,6 +502,7 @@ impl List {
                             settings,
                             selected_device,
                             list_update_state,
+                            queue,
                             Message::List(i, RowMessage::ToggleSelection(selected)),
                         );
                     }
@@ -184,6 +524,11 @@ impl List {
                 Self::filter_package_lists(self);
                 Command::none()
             }
+            Message::NetworkStateSelected(network_state) => {
+                self.selected_network_state = Some(network_state);
+                Self::filter_package_lists(self);
+                Command::none()
+            }
             Message::RemovalSelected(removal) => {
                 self.selected_removal = Some(removal);
                 Self::filter_package_lists(self);
@@ -227,17 +572,64 @@ impl List {
                         }
                         Command::none()
                     }
+                    // Boot-critical packages can only be removed from the review modal
+                    RowMessage::ActionPressed
+                        if package.boot_critical && package.state == PackageState::Enabled =>
+                    {
+                        let _ = self.update(
+                            settings,
+                            selected_device,
+                            list_update_state,
+                            queue,
+                            Message::List(i_package, RowMessage::ToggleSelection(true)),
+                        );
+                        self.update(
+                            settings,
+                            selected_device,
+                            list_update_state,
+                            queue,
+                            Message::ApplyActionOnSelection,
+                        )
+                    }
                     RowMessage::ActionPressed => {
                         self.phone_packages[i_user][i_package].selected = true;
-                        Command::batch(build_action_pkg_commands(
+                        let (jobs, targets) = build_action_pkg_jobs(
                             &self.phone_packages,
                             selected_device,
                             &settings.device,
                             (i_user, i_package),
-                        ))
+                        );
+                        // A single action is a batch of its own, unless a batch is
+                        // already running: don't end it before its last package
+                        if self.batch.is_none() {
+                            self.clear_failures();
+                        }
+                        if !targets.is_empty() {
+                            let batch = self.batch.get_or_insert_with(Batch::default);
+                            batch.pending += targets.len();
+                            batch.targets.extend(targets);
+                        }
+                        queue.push(jobs);
+                        Command::none()
+                    }
+                    RowMessage::RetryPressed => {
+                        package.error = None;
+                        self.update(
+                            settings,
+                            selected_device,
+                            list_update_state,
+                            queue,
+                            Message::List(i_package, RowMessage::ActionPressed),
+                        )
                     }
+                    RowMessage::NetworkPressed => build_network_pkg_command(
+                        &self.phone_packages,
+                        selected_device,
+                        (i_user, i_package),
+                    ),
                     RowMessage::PackagePressed => {
                         self.description = package.clone().description;
+                        self.overlay_status = None;
                         package.current = true;
                         if self.current_package_index != i_package {
                             self.phone_packages[i_user][self.current_package_index].current = false;
@@ -249,34 +641,398 @@ impl List {
             }
             Message::ApplyActionOnSelection => {
                 self.selection_modal = true;
-                Command::none()
+                Command::perform(measure_storage(), |(sizes, free_space)| {
+                    Message::PackagesSizeLoaded(sizes, free_space)
+                })
             }
             Message::UserSelected(user) => {
-                self.selected_user = Some(user);
                 self.filtered_packages = (0..self.phone_packages[user.index].len()).collect();
+                self.selected_user = Some(user);
                 Self::filter_package_lists(self);
                 Command::none()
             }
-            Message::ChangePackageState(res) => {
-                if let Ok(CommandType::PackageManager(p)) = res {
-                    let package = &mut self.phone_packages[p.i_user][p.index];
-                    package.state = package.state.opposite(settings.device.disable_mode);
-                    package.selected = false;
-                    self.selected_packages
-                        .retain(|&x| x.1 != p.index && x.0 != p.i_user);
-                    Self::filter_package_lists(self);
+            Message::ChangePackageState(CommandType::Shell, _) => {
+                self.batch_jobs_done(1, selected_device)
+            }
+            Message::ChangePackageState(CommandType::PackageManager(p), Err(err)) => {
+                self.phone_packages[p.i_user][p.index].error = Some(err.clone());
+                if let Some(batch) = self.batch.as_mut() {
+                    batch.errors.push(err);
                 }
+                Self::filter_package_lists(self);
+                self.batch_jobs_done(1, selected_device)
+            }
+            Message::ChangePackageState(CommandType::PackageManager(p), Ok(())) => {
+                let package = &mut self.phone_packages[p.i_user][p.index];
+                package.state = package.state.opposite(settings.device.removal_state());
+                package.error = None;
+                package.selected = false;
+                self.selected_packages
+                    .retain(|&x| x.1 != p.index && x.0 != p.i_user);
+                Self::filter_package_lists(self);
+                if let Some(batch) = self.batch.as_mut() {
+                    batch.changed.push((p.i_user, p.index));
+                }
+                self.batch_jobs_done(1, selected_device)
+            }
+            Message::JobsCancelled(nb) => self.batch_jobs_done(nb, selected_device),
+            Message::CopyUserStates(source_id, target_id) => {
+                let index_of = |id| {
+                    selected_device
+                        .user_list
+                        .iter()
+                        .find(|u| u.id == id)
+                        .map(|u| u.index)
+                };
+                let (Some(source), Some(target)) = (index_of(source_id), index_of(target_id))
+                else {
+                    warn!("[USERS] user {} or {} not found", source_id, target_id);
+                    return Command::none();
+                };
+                let wanted = self.user_alignment(source, target, None);
+                info!(
+                    "[USERS] Copying {} package state(s) of user {} to user {}",
+                    wanted.len(),
+                    source_id,
+                    target_id
+                );
+                self.start_state_batch(&wanted, selected_device, settings, queue);
                 Command::none()
             }
-            Message::ModalUserSelected(user) => {
-                self.selected_user = Some(user);
+            Message::OverlayPressed(remove) => {
+                let code_path = &self.phone_packages[i_user][self.current_package_index].code_path;
+                let command = if remove {
+                    overlay_remove_command(code_path)
+                } else {
+                    overlay_restore_command(code_path)
+                };
+                match command {
+                    Some(command) if settings.device.root_mode => Command::perform(
+                        perform_adb_commands(command, CommandType::Shell),
+                        Message::OverlayChanged,
+                    ),
+                    _ => Command::none(),
+                }
+            }
+            Message::OverlayChanged(res) => {
+                self.overlay_status = Some(match res {
+                    Ok(_) => tr("Done: reboot the device to apply the change"),
+                    Err(err) => err.to_string(),
+                });
+                Command::none()
+            }
+            Message::UserMatrixToggled(toggled) => {
+                self.user_matrix = toggled;
+                Command::none()
+            }
+            Message::MatrixDiffersToggled(toggled) => {
+                self.matrix_differs = toggled;
+                Command::none()
+            }
+            Message::MatrixReferenceSelected(user) => {
+                self.matrix_reference = Some(user);
+                Command::none()
+            }
+            Message::AlignUsers => {
+                let users = matrix_users(selected_device);
+                let reference = self
+                    .matrix_reference
+                    .as_ref()
+                    .or_else(|| users.first().copied())
+                    .map_or(0, |u| u.index);
+                let columns: Vec<States> =
+                    users.iter().map(|u| self.user_states(u.index)).collect();
+                let names: HashSet<&str> = self.matrix_names(&columns).into_iter().collect();
+                let wanted: Vec<(usize, usize, PackageState)> = users
+                    .iter()
+                    .filter(|u| u.index != reference)
+                    .flat_map(|u| self.user_alignment(reference, u.index, Some(&names)))
+                    .collect();
+                info!(
+                    "[USERS] Aligning {} package state(s) on user index {}",
+                    wanted.len(),
+                    reference
+                );
+                self.start_state_batch(&wanted, selected_device, settings, queue);
+                Command::none()
+            }
+            Message::ApplyStates(user_id, changes) => {
+                // The states may be older than the package list
+                let LoadingState::Ready(_) = self.loading_state else {
+                    return Command::none();
+                };
+                let Some(i_target) = selected_device
+                    .user_list
+                    .iter()
+                    .find(|u| u.id == user_id)
+                    .map(|u| u.index)
+                else {
+                    warn!("[USERS] user {} not found", user_id);
+                    return Command::none();
+                };
+                let Some(packages) = self.phone_packages.get(i_target) else {
+                    return Command::none();
+                };
+                let indexes: HashMap<&str, usize> = packages
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| (p.name.as_str(), i))
+                    .collect();
+                let wanted: Vec<(usize, usize, PackageState)> = changes
+                    .iter()
+                    .filter_map(|(name, state)| {
+                        let index = *indexes.get(name.as_str())?;
+                        (packages[index].state != *state).then_some((i_target, index, *state))
+                    })
+                    .collect();
+                info!(
+                    "[STATES] Applying {} package state(s) to user {}",
+                    wanted.len(),
+                    user_id
+                );
+                self.start_state_batch(&wanted, selected_device, settings, queue);
+                Command::none()
+            }
+            Message::ApplyRecommendations(user_id) => Command::perform(
+                get_device_profile(selected_device.android_sdk),
+                move |profile| Message::UserProfileLoaded(user_id, profile),
+            ),
+            Message::UserProfileLoaded(user_id, profile) => {
+                let Some(packages) = selected_device
+                    .user_list
+                    .iter()
+                    .find(|u| u.id == user_id)
+                    .and_then(|u| self.phone_packages.get(u.index))
+                else {
+                    return Command::none();
+                };
+                let removal_state = settings.device.removal_state();
+                let changes =
+                    recommend(packages, &profile, removal_state, settings.device.root_mode)
+                        .into_iter()
+                        .map(|r| (r.name, removal_state))
+                        .collect();
+                self.update(
+                    settings,
+                    selected_device,
+                    list_update_state,
+                    queue,
+                    Message::ApplyStates(user_id, changes),
+                )
+            }
+            Message::FailedFilterToggled(toggled) => {
+                self.failed_filter = toggled;
+                Self::filter_package_lists(self);
+                Command::none()
+            }
+            Message::BatchVerified(states, free_space) => {
+                let Some(batch) = self.batch.take() else {
+                    return Command::none();
+                };
+                // Actions started during the verification: wait for the next one
+                if batch.pending > 0 {
+                    self.batch = Some(batch);
+                    return Command::none();
+                }
+
+                let mut mismatches = vec![];
+                for &(i_user, index, expected) in &batch.targets {
+                    let package = &mut self.phone_packages[i_user][index];
+                    let Some(&state) = states
+                        .iter()
+                        .find(|(i, _)| *i == i_user)
+                        .and_then(|(_, s)| s.get(&package.name))
+                    else {
+                        continue;
+                    };
+                    package.state = state;
+                    if state != expected {
+                        let user = selected_device
+                            .user_list
+                            .iter()
+                            .find(|u| u.index == i_user)
+                            .map_or(0, |u| u.id);
+                        error!(
+                            "[VERIFY] {} (user {}): expected {:?}, found {:?}",
+                            package.name, user, expected, state
+                        );
+                        mismatches.push(format!(
+                            "{} (user {user}): {} -> {}",
+                            package.name, expected, state
+                        ));
+                        // A failure even if the command said otherwise: keep its error if any
+                        if package.error.is_none() {
+                            package.error = Some(AdbError::Failed(
+                                tr("expected {}, found {}")
+                                    .replacen("{}", &expected.to_string(), 1)
+                                    .replacen("{}", &state.to_string(), 1),
+                            ));
+                        }
+                    }
+                }
+                Self::filter_package_lists(self);
+
+                let mut report = self.compute_batch_report(&batch, free_space);
+                report.mismatches = mismatches;
+                for err in &batch.errors {
+                    let message = err.to_string();
+                    match report.errors.iter_mut().find(|(m, _)| *m == message) {
+                        Some((_, nb)) => *nb += 1,
+                        None => report.errors.push((message, 1)),
+                    }
+                }
+                log_batch_report(&report);
+                let review = batch.review;
+                if review || !report.mismatches.is_empty() || !report.errors.is_empty() {
+                    self.batch_report = Some(report);
+                }
+                if review && settings.device.trial_mode {
+                    return self.start_trial(&batch, selected_device, settings.device.root_mode);
+                }
+                Command::none()
+            }
+            Message::ChangeNetworkState(CommandType::PackageManager(p), state, res) => {
+                let package = &mut self.phone_packages[p.i_user][p.index];
+                // The state the device really has, even after a partial failure
+                if let Some(state) = state {
+                    package.network = state;
+                }
+                package.error = res.err();
+                Self::filter_package_lists(self);
+                Command::none()
+            }
+            Message::ChangeNetworkState(CommandType::Shell, ..) => Command::none(),
+            Message::ModalUserSelected(index) => {
+                let Some(user) = selected_device
+                    .user_list
+                    .iter()
+                    .find(|u| u.index == index)
+                    .cloned()
+                else {
+                    return Command::none();
+                };
                 self.update(
                     settings,
                     selected_device,
                     list_update_state,
+                    queue,
                     Message::UserSelected(user),
                 )
             }
+            Message::ShowComponents => {
+                let package = self.phone_packages[i_user][self.current_package_index]
+                    .name
+                    .clone();
+                self.components = None;
+                self.components_modal = true;
+                Command::perform(
+                    get_package_components(
+                        package,
+                        self.selected_user.as_ref().map_or(0, |u| u.id),
+                    ),
+                    Message::ComponentsLoaded,
+                )
+            }
+            Message::ComponentsLoaded(components) => {
+                self.components = Some(components);
+                Command::none()
+            }
+            Message::ToggleComponent(component, enable) => {
+                let package = &self.phone_packages[i_user][self.current_package_index];
+                let p_info = PackageInfo {
+                    i_user,
+                    index: self.current_package_index,
+                    removal: package.removal.to_string(),
+                };
+                Command::batch(
+                    apply_component_commands(
+                        &package.name,
+                        &component,
+                        enable,
+                        &self.selected_user.clone().unwrap_or_default(),
+                        selected_device,
+                        settings.device.root_mode,
+                    )
+                    .into_iter()
+                    .map(|action| {
+                        let component = component.clone();
+                        Command::perform(
+                            perform_adb_commands(
+                                action,
+                                CommandType::PackageManager(p_info.clone()),
+                            ),
+                            move |res| Message::ComponentChanged(component, enable, res),
+                        )
+                    }),
+                )
+            }
+            // The components may have been reloaded (e.g. for another package) in the meantime
+            Message::ComponentChanged(name, enabled, res) => {
+                if let Ok(CommandType::PackageManager(p)) = res {
+                    if let Some(component) = self
+                        .components
+                        .as_mut()
+                        .and_then(|c| c.iter_mut().find(|c| c.name == name))
+                    {
+                        component.enabled = enabled;
+                    }
+                    if let Some(package) = self
+                        .phone_packages
+                        .get_mut(p.i_user)
+                        .and_then(|u| u.get_mut(p.index))
+                    {
+                        package.disabled_components.retain(|c| *c != name);
+                        if !enabled {
+                            package.disabled_components.push(name);
+                        }
+                    }
+                }
+                Command::none()
+            }
+            Message::RevertToFactory => Command::batch(build_uninstall_updates_commands(
+                &self.phone_packages,
+                selected_device,
+                (i_user, self.current_package_index),
+            )),
+            Message::StripSelectedUpdates => {
+                // Updates are shared by all users
+                let mut selection: Vec<(usize, usize)> = self
+                    .selected_packages
+                    .iter()
+                    .filter(|s| self.phone_packages[s.0][s.1].update_path.is_some())
+                    .copied()
+                    .collect();
+                selection.sort_unstable_by_key(|s| s.1);
+                selection.dedup_by_key(|s| s.1);
+
+                let device = &*selected_device;
+                Command::batch(selection.into_iter().flat_map(|s| {
+                    build_uninstall_updates_commands(&self.phone_packages, device, s)
+                }))
+            }
+            Message::SystemUpdateRemoved(CommandType::PackageManager(p), res) => {
+                let (nb, nb_failed, reclaimed) = self.updates_recap.unwrap_or_default();
+                match res {
+                    Ok(size) => {
+                        let name = self.phone_packages[p.i_user][p.index].name.clone();
+                        for user_packages in &mut self.phone_packages {
+                            if let Some(package) = user_packages.iter_mut().find(|x| x.name == name)
+                            {
+                                package.update_path = None;
+                                package.error = None;
+                            }
+                        }
+                        self.updates_recap = Some((nb + 1, nb_failed, reclaimed + size));
+                    }
+                    Err(err) => {
+                        self.phone_packages[p.i_user][p.index].error = Some(err);
+                        self.updates_recap = Some((nb, nb_failed + 1, reclaimed));
+                    }
+                }
+                Self::filter_package_lists(self);
+                Command::none()
+            }
+            Message::SystemUpdateRemoved(CommandType::Shell, _) => Command::none(),
             Message::Nothing => Command::none(),
         }
     }
@@ -288,28 +1044,28 @@ impl List {
     ) -> Element<Message, Renderer<Theme>> {
         match &self.loading_state {
             LoadingState::DownloadingList(_) => {
-                let text = "Downloading latest UAD lists from Github. Please wait...";
+                let text = &tr("Downloading latest UAD lists from Github. Please wait...");
                 waiting_view(settings, text, true)
             }
             LoadingState::FindingPhones(_) => {
-                let text = "Finding connected devices...";
+                let text = &tr("Finding connected devices...");
                 waiting_view(settings, text, false)
             }
             LoadingState::LoadingPackages(_) => {
-                let text = "Pulling packages from the device. Please wait...";
+                let text = &tr("Pulling packages from the device. Please wait...");
                 waiting_view(settings, text, false)
             }
             LoadingState::_UpdatingUad(_) => {
-                let text = "Updating UAD. Please wait...";
+                let text = &tr("Updating UAD. Please wait...");
                 waiting_view(settings, text, false)
             }
             LoadingState::RestoringDevice(output) => {
-                let text = format!("Restoring device: {output}");
+                let text = tr("Restoring device: {}").replacen("{}", &output, 1);
                 waiting_view(settings, &text, false)
             }
             LoadingState::Ready(_) => {
                 let search_packages = text_input(
-                    "Search packages...",
+                    &tr("Search packages... (e.g. state:enabled removal:recommended oem)"),
                     &self.input_value,
                     Message::SearchInputChanged,
                 )
@@ -317,10 +1073,10 @@ impl List {
 
                 let user_picklist = pick_list(
                     selected_device.user_list.clone(),
-                    self.selected_user,
+                    self.selected_user.clone(),
                     Message::UserSelected,
                 )
-                .width(85);
+                .width(220);
 
                 let divider = Space::new(Length::Fill, Length::Shrink);
 
@@ -332,18 +1088,53 @@ impl List {
                     Message::PackageStateSelected,
                 );
 
+                let network_state_picklist = pick_list(
+                    &NetworkState::ALL[..],
+                    self.selected_network_state,
+                    Message::NetworkStateSelected,
+                );
+
                 let removal_picklist = pick_list(
                     &Removal::ALL[..],
                     self.selected_removal,
                     Message::RemovalSelected,
                 );
 
+                let nb_failed = self.phone_packages[self.selected_user.as_ref().unwrap().index]
+                    .iter()
+                    .filter(|p| p.error.is_some())
+                    .count();
+                let failed_checkbox = if nb_failed > 0 || self.failed_filter {
+                    row![checkbox(
+                        tr("Failed ({})").replacen("{}", &nb_failed.to_string(), 1),
+                        self.failed_filter,
+                        Message::FailedFilterToggled,
+                    )
+                    .style(style::CheckBox::SettingsEnabled)]
+                } else {
+                    row![]
+                };
+
+                let matrix_checkbox = if selected_device.user_list.len() > 1 {
+                    row![checkbox(
+                        tr("Compare users"),
+                        self.user_matrix,
+                        Message::UserMatrixToggled,
+                    )
+                    .style(style::CheckBox::SettingsEnabled)]
+                } else {
+                    row![]
+                };
+
                 let control_panel = row![
                     search_packages,
                     user_picklist,
                     divider,
+                    matrix_checkbox,
+                    failed_checkbox,
                     removal_picklist,
                     package_state_picklist,
+                    network_state_picklist,
                     list_picklist,
                 ]
                 .width(Length::Fill)
@@ -356,88 +1147,283 @@ impl List {
                         .iter()
                         .fold(column![].spacing(6), |col, i| {
                             col.push(
-                                self.phone_packages[self.selected_user.unwrap().index][*i]
+                                self.phone_packages[self.selected_user.as_ref().unwrap().index][*i]
                                     .view(settings, selected_device)
                                     .map(move |msg| Message::List(*i, msg)),
                             )
                         });
 
-                let packages_scrollable = scrollable(packages)
-                    .height(Length::FillPortion(6))
-                    .style(style::Scrollable::Packages);
+                let packages_scrollable: Element<Message, Renderer<Theme>> =
+                    if self.user_matrix && selected_device.user_list.len() > 1 {
+                        self.user_matrix_view(selected_device)
+                    } else {
+                        scrollable(packages)
+                            .height(Length::FillPortion(6))
+                            .style(style::Scrollable::Packages)
+                            .into()
+                    };
 
                 let description_scroll = scrollable(text(&self.description).width(Length::Fill))
                     .style(style::Scrollable::Description);
 
-                let description_panel = container(description_scroll)
-                    .padding(6)
-                    .height(Length::FillPortion(2))
-                    .width(Length::Fill)
-                    .style(style::Container::Frame);
+                let description_header = if self.description.is_empty() {
+                    row![]
+                } else {
+                    let package = &self.phone_packages[self.selected_user.as_ref().unwrap().index]
+                        [self.current_package_index];
+                    let header = row![
+                        text(&package.name).style(style::Text::Commentary),
+                        text(
+                            tr("Source: {}").replacen(
+                                "{}",
+                                &package
+                                    .source
+                                    .clone()
+                                    .unwrap_or_else(|| tr("official list")),
+                                1
+                            )
+                        )
+                        .style(style::Text::Commentary),
+                        Space::new(Length::Fill, Length::Shrink),
+                    ]
+                    .spacing(10)
+                    .align_items(Alignment::Center);
+
+                    let header = if package.update_path.is_some() {
+                        header.push(
+                            button(text(tr("Revert to factory version")))
+                                .padding(5)
+                                .on_press(Message::RevertToFactory)
+                                .style(style::Button::Primary),
+                        )
+                    } else {
+                        header
+                    };
+
+                    // Systemless removal of system apps (Magisk)
+                    let header = if settings.device.root_mode
+                        && overlay_remove_command(&package.code_path).is_some()
+                    {
+                        header
+                            .push(text(self.overlay_status.clone().unwrap_or_default()))
+                            .push(
+                                button(text(tr("Remove from system")))
+                                    .padding(5)
+                                    .on_press(Message::OverlayPressed(true))
+                                    .style(style::Button::UninstallPackage),
+                            )
+                            .push(
+                                button(text(tr("Put back in system")))
+                                    .padding(5)
+                                    .on_press(Message::OverlayPressed(false))
+                                    .style(style::Button::RestorePackage),
+                            )
+                    } else {
+                        header
+                    };
+
+                    header.push(
+                        button(text(tr("Components")))
+                            .padding(5)
+                            .on_press(Message::ShowComponents)
+                            .style(style::Button::Primary),
+                    )
+                };
+
+                let description_panel =
+                    container(column![description_header, description_scroll].spacing(6))
+                        .padding(6)
+                        .height(Length::FillPortion(2))
+                        .width(Length::Fill)
+                        .style(style::Container::Frame);
 
                 let review_selection = if !self.selected_packages.is_empty() {
-                    button(text(format!(
-                        "Review selection ({})",
-                        self.selected_packages.len()
+                    button(text(tr("Review selection ({})").replacen(
+                        "{}",
+                        &self.selected_packages.len().to_string(),
+                        1,
                     )))
                     .on_press(Message::ApplyActionOnSelection)
                     .padding(5)
                     .style(style::Button::Primary)
                 } else {
-                    button(text(format!(
-                        "Review selection ({})",
-                        self.selected_packages.len()
+                    button(text(tr("Review selection ({})").replacen(
+                        "{}",
+                        &self.selected_packages.len().to_string(),
+                        1,
                     )))
                     .padding(5)
                 };
 
-                let select_all_btn = button("Select all")
+                let select_all_btn = button(text(tr("Select all")))
                     .padding(5)
                     .on_press(Message::ToggleAllSelected(true))
                     .style(style::Button::Primary);
 
-                let unselect_all_btn = button("Unselect all")
+                let unselect_all_btn = button(text(tr("Unselect all")))
                     .padding(5)
                     .on_press(Message::ToggleAllSelected(false))
                     .style(style::Button::Primary);
 
-                let action_row = row![
-                    select_all_btn,
-                    unselect_all_btn,
-                    Space::new(Length::Fill, Length::Shrink),
-                    review_selection,
-                ]
-                .width(Length::Fill)
-                .spacing(10)
-                .align_items(Alignment::Center);
+                let nb_selected_updates = self
+                    .selected_packages
+                    .iter()
+                    .filter(|s| self.phone_packages[s.0][s.1].update_path.is_some())
+                    .map(|s| s.1)
+                    .collect::<HashSet<usize>>()
+                    .len();
 
-                let unavailable = container(
-                    column![
-                        text("ADB is not authorized to access this user!").size(22)
-                            .style(style::Text::Danger),
-                        text("The most likely reason is that it is the user of your work profile (also called Secure Folder on Samsung devices). There's really no solution, other than completely disabling your work profile in your device settings.")
-                            .style(style::Text::Commentary)
-                            .horizontal_alignment(alignment::Horizontal::Center),
-                    ]
-                    .spacing(6)
-                    .align_items(Alignment::Center)
-                )
-                .padding(10)
+                let strip_updates_btn = if nb_selected_updates == 0 {
+                    button(text(tr("Strip updates ({})").replacen(
+                        "{}",
+                        &nb_selected_updates.to_string(),
+                        1,
+                    )))
+                    .padding(5)
+                } else {
+                    button(text(tr("Strip updates ({})").replacen(
+                        "{}",
+                        &nb_selected_updates.to_string(),
+                        1,
+                    )))
+                    .on_press(Message::StripSelectedUpdates)
+                    .padding(5)
+                    .style(style::Button::Primary)
+                };
+
+                let updates_recap_text = match self.updates_recap {
+                    Some((nb, 0, reclaimed)) => text(
+                        tr("{} system update(s) removed: {} reclaimed")
+                            .replacen("{}", &nb.to_string(), 1)
+                            .replacen("{}", &format_size(reclaimed), 1),
+                    )
+                    .style(style::Text::Ok),
+                    Some((nb, nb_failed, reclaimed)) => text(
+                        tr("{} system update(s) removed: {} reclaimed, {} failed")
+                            .replacen("{}", &nb.to_string(), 1)
+                            .replacen("{}", &format_size(reclaimed), 1)
+                            .replacen("{}", &nb_failed.to_string(), 1),
+                    )
+                    .style(style::Text::Danger),
+                    None => text(""),
+                };
+
+                let list_changes_btn = if self.list_changes.is_empty() {
+                    button(text(tr("No list changes"))).padding(5)
+                } else {
+                    button(text(tr("List changes ({})").replacen(
+                        "{}",
+                        &self.list_changes.len().to_string(),
+                        1,
+                    )))
+                    .on_press(Message::ShowListChanges)
+                    .padding(5)
+                    .style(style::Button::Primary)
+                };
+
+                let contribute_btn = button(text(tr("Contribute")))
+                    .on_press(Message::ShowContribute)
+                    .padding(5)
+                    .style(style::Button::Primary);
+
+                let recommend_btn = button(text(tr("Recommend")))
+                    .on_press(Message::Recommend)
+                    .padding(5)
+                    .style(style::Button::Primary);
+
+                let action_row = row![
+                    select_all_btn,
+                    unselect_all_btn,
+                    recommend_btn,
+                    list_changes_btn,
+                    contribute_btn,
+                    Space::new(Length::Fill, Length::Shrink),
+                    updates_recap_text,
+                    strip_updates_btn,
+                    review_selection,
+                ]
+                .width(Length::Fill)
+                .spacing(10)
+                .align_items(Alignment::Center);
+
+                let unavailable = container(
+                    column![
+                        text(tr("ADB is not authorized to access this user!")).size(22)
+                            .style(style::Text::Danger),
+                        text(tr("The most likely reason is that it is the user of your work profile (also called Secure Folder on Samsung devices). There's really no solution, other than completely disabling your work profile in your device settings."))
+                            .style(style::Text::Commentary)
+                            .horizontal_alignment(alignment::Horizontal::Center),
+                    ]
+                    .spacing(6)
+                    .align_items(Alignment::Center)
+                )
+                .padding(10)
                 .center_x()
                 .style(style::Container::BorderedFrame);
 
                 let content = if selected_device.user_list.is_empty()
-                    || !self.phone_packages[self.selected_user.unwrap().index].is_empty()
+                    || !self.phone_packages[self.selected_user.as_ref().unwrap().index].is_empty()
                 {
-                    column![
-                        control_panel,
-                        packages_scrollable,
-                        description_panel,
-                        action_row,
-                    ]
-                    .width(Length::Fill)
-                    .spacing(10)
-                    .align_items(Alignment::Center)
+                    let content = column![control_panel]
+                        .width(Length::Fill)
+                        .spacing(10)
+                        .align_items(Alignment::Center);
+                    let content = if let Some(e) = &self.list_error {
+                        content.push(
+                            container(
+                                text(
+                                    tr("The cached debloat list is corrupted ({}). It has been \
+                                    moved to {} and the list embedded in UAD (possibly outdated) \
+                                    is used instead.")
+                                    .replacen("{}", &e.to_string(), 1)
+                                    .replacen(
+                                        "{}",
+                                        &CACHE_DIR
+                                            .join("uad_lists.json.corrupted")
+                                            .display()
+                                            .to_string(),
+                                        1,
+                                    ),
+                                )
+                                .style(style::Text::Danger),
+                            )
+                            .padding(10)
+                            .width(Length::Fill)
+                            .style(style::Container::BorderedFrame),
+                        )
+                    } else {
+                        content
+                    };
+                    let user_warnings: Vec<String> = self
+                        .selected_user
+                        .iter()
+                        .flat_map(|u| {
+                            let not_running = (!u.running).then(|| {
+                                tr("This user is not running: changes apply the next time it starts")
+                            });
+                            u.kind.warning().into_iter().chain(not_running)
+                        })
+                        .collect();
+                    let content = if user_warnings.is_empty() {
+                        content
+                    } else {
+                        content.push(
+                            container(
+                                user_warnings
+                                    .into_iter()
+                                    .fold(column![].spacing(5), |col, w| {
+                                        col.push(text(w).style(style::Text::Danger))
+                                    }),
+                            )
+                            .padding(10)
+                            .width(Length::Fill)
+                            .style(style::Container::BorderedFrame),
+                        )
+                    };
+                    content
+                        .push(packages_scrollable)
+                        .push(description_panel)
+                        .push(action_row)
                 } else {
                     column![
                         control_panel,
@@ -453,7 +1439,38 @@ impl List {
                         self.apply_selection_modal(
                             selected_device,
                             settings,
-                            &self.phone_packages[self.selected_user.unwrap().index],
+                            &self.phone_packages[self.selected_user.as_ref().unwrap().index],
+                        ),
+                    )
+                    .on_blur(Message::ModalHide)
+                    .into()
+                } else if let Some(report) = &self.batch_report {
+                    Modal::new(content.padding(10), batch_report_modal(report))
+                        .on_blur(Message::ModalHide)
+                        .into()
+                } else if let Some(trial) = &self.trial {
+                    Modal::new(content.padding(10), trial_view(trial))
+                        .on_blur(Message::ModalHide)
+                        .into()
+                } else if self.recommend_modal {
+                    Modal::new(content.padding(10), self.recommend_view())
+                        .on_blur(Message::ModalHide)
+                        .into()
+                } else if self.contribute_modal {
+                    Modal::new(content.padding(10), self.contribute_view())
+                        .on_blur(Message::ModalHide)
+                        .into()
+                } else if self.list_changes_modal {
+                    Modal::new(content.padding(10), self.list_changes_view())
+                        .on_blur(Message::ModalHide)
+                        .into()
+                } else if self.components_modal {
+                    Modal::new(
+                        content.padding(10),
+                        self.package_components_modal(
+                            settings,
+                            &self.phone_packages[self.selected_user.as_ref().unwrap().index]
+                                [self.current_package_index],
                         ),
                     )
                     .on_blur(Message::ModalHide)
@@ -473,11 +1490,22 @@ impl List {
     ) -> Element<Message, Renderer<Theme>> {
         // (nb_to_restore, nb_to_remove)
         let mut h_recap: HashMap<Removal, (u8, u8)> = HashMap::new();
+        // Space freed by the removal of packages (suspended packages keep their data)
+        let mut h_sizes: HashMap<Removal, u64> = HashMap::new();
         for p in packages.iter().filter(|p| p.selected) {
-            if p.state == PackageState::Uninstalled || p.state == PackageState::Disabled {
+            if p.state == PackageState::Uninstalled
+                || p.state == PackageState::Disabled
+                || p.state == PackageState::Suspended
+            {
                 h_recap.entry(p.removal).or_insert((0, 0)).1 += 1;
             } else {
                 h_recap.entry(p.removal).or_insert((0, 0)).0 += 1;
+                if settings.device.removal_state() != PackageState::Suspended {
+                    *h_sizes.entry(p.removal).or_default() += self
+                        .packages_size
+                        .get(&p.name)
+                        .map_or(0, |s| s.data + s.cache);
+                }
             }
         }
 
@@ -486,9 +1514,9 @@ impl List {
             |row, user| {
                 row.push(
                     radio(
-                        format!("{}", user.clone()),
-                        *user,
-                        self.selected_user,
+                        user.to_string(),
+                        user.index,
+                        self.selected_user.as_ref().map(|u| u.index),
                         Message::ModalUserSelected,
                     )
                     .size(23),
@@ -496,13 +1524,14 @@ impl List {
             },
         );
 
-        let title_ctn =
-            container(row![text("Review your selection").size(25)].align_items(Alignment::Center))
-                .width(Length::Fill)
-                .style(style::Container::Frame)
-                .padding([10, 0, 10, 0])
-                .center_y()
-                .center_x();
+        let title_ctn = container(
+            row![text(tr("Review your selection")).size(25)].align_items(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .style(style::Container::Frame)
+        .padding([10, 0, 10, 0])
+        .center_y()
+        .center_x();
 
         let users_ctn = container(radio_btn_users)
             .padding(10)
@@ -511,8 +1540,10 @@ impl List {
 
         let explaination_ctn = container(
             row![
-                text("The action for the selected user will be applied to all other users")
-                    .style(style::Text::Danger),
+                text(tr(
+                    "The action for the selected user will be applied to all other users"
+                ))
+                .style(style::Text::Danger),
                 tooltip(
                     text("\u{EA0C}")
                         .font(ICONS)
@@ -520,9 +1551,11 @@ impl List {
                         .horizontal_alignment(alignment::Horizontal::Center)
                         .style(style::Text::Commentary)
                         .size(17),
-                    "Let's say you choose user 0. If a selected package on user 0\n\
+                    tr(
+                        "Let's say you choose user 0. If a selected package on user 0\n\
                         is set to be uninstalled and if this same package is disabled on user 10,\n\
-                        then the package on both users will be uninstalled.",
+                        then the package on both users will be uninstalled."
+                    ),
                     tooltip::Position::Top,
                 )
                 .gap(20)
@@ -536,19 +1569,62 @@ impl List {
         .padding(10)
         .style(style::Container::BorderedFrame);
 
-        let modal_btn_row = row![
-            button(text("Cancel")).on_press(Message::ModalHide),
+        let critical = self.boot_critical_selection();
+        let confirmed = critical.is_empty() || self.critical_confirmation.trim() == device.model;
+        let apply_btn = if confirmed {
+            button(text(tr("Apply"))).on_press(Message::ModalValidate)
+        } else {
+            button(text(tr("Apply")))
+        };
+        let btn_row = row![
+            button(text(tr("Cancel"))).on_press(Message::ModalHide),
             horizontal_space(Length::Fill),
-            button(text("Apply")).on_press(Message::ModalValidate),
+            apply_btn,
         ]
         .padding([0, 15, 10, 10]);
 
-        let recap_view = Removal::ALL
-            .iter()
-            .filter(|&&r| r != Removal::All)
-            .fold(column![].spacing(6).width(Length::Fill), |col, r| {
-                col.push(recap(settings, &mut h_recap, *r))
-            });
+        let modal_btn_row = if critical.is_empty() {
+            column![btn_row]
+        } else {
+            let critical_ctn = container(
+                column![
+                    text(tr(
+                        "Removing these packages can prevent the device from booting (bootloop):"
+                    ))
+                    .style(style::Text::Danger),
+                    text(critical.join(", ")),
+                    text(tr("Type the model of the device ({}) to confirm").replacen(
+                        "{}",
+                        &device.model,
+                        1
+                    ))
+                    .style(style::Text::Commentary),
+                    text_input(
+                        &device.model,
+                        &self.critical_confirmation,
+                        Message::CriticalConfirmationChanged
+                    )
+                    .padding(5),
+                ]
+                .spacing(6),
+            )
+            .padding(10)
+            .width(Length::Fill)
+            .style(style::Container::BorderedFrame);
+            column![row![critical_ctn].padding([0, 10, 0, 10]), btn_row].spacing(10)
+        };
+
+        let recap_view = Removal::ALL.iter().filter(|&&r| r != Removal::All).fold(
+            column![].spacing(6).width(Length::Fill),
+            |col, r| {
+                col.push(recap(
+                    settings,
+                    &mut h_recap,
+                    h_sizes.get(r).copied().unwrap_or_default(),
+                    *r,
+                ))
+            },
+        );
 
         let selected_pkgs_ctn = container(
             container(
@@ -557,15 +1633,15 @@ impl List {
                         if !self
                             .selected_packages
                             .iter()
-                            .any(|s| s.0 == self.selected_user.unwrap().index)
+                            .any(|s| s.0 == self.selected_user.as_ref().unwrap().index)
                         {
-                            column![text("No packages selected for this user")]
+                            column![text(tr("No packages selected for this user"))]
                                 .align_items(Alignment::Center)
                                 .width(Length::Fill)
                         } else {
                             self.selected_packages
                                 .iter()
-                                .filter(|s| s.0 == self.selected_user.unwrap().index)
+                                .filter(|s| s.0 == self.selected_user.as_ref().unwrap().index)
                                 .fold(
                                     column![].spacing(6).width(Length::Fill),
                                     |col, selection| {
@@ -587,26 +1663,25 @@ impl List {
                                                         .clone()
                                                 ),],
                                                 horizontal_space(Length::Fill),
-                                                row![match self.phone_packages[selection.0]
-                                                    [selection.1]
-                                                    .state
-                                                {
-                                                    PackageState::Enabled =>
-                                                        if settings.device.disable_mode {
-                                                            text("Disable")
-                                                                .style(style::Text::Danger)
+                                                row![{
+                                                    let state = self.phone_packages[selection.0]
+                                                        [selection.1]
+                                                        .state;
+                                                    text(tr(action_text_for(
+                                                        state,
+                                                        settings.device.removal_state(),
+                                                    )))
+                                                    .style(
+                                                        if state == PackageState::Enabled
+                                                            || state == PackageState::All
+                                                        {
+                                                            style::Text::Danger
                                                         } else {
-                                                            text("Uninstall")
-                                                                .style(style::Text::Danger)
+                                                            style::Text::Ok
                                                         },
-                                                    PackageState::Disabled =>
-                                                        text("Enable").style(style::Text::Ok),
-                                                    PackageState::Uninstalled =>
-                                                        text("Restore").style(style::Text::Ok),
-                                                    PackageState::All => text("Impossible")
-                                                        .style(style::Text::Danger),
+                                                    )
                                                 },]
-                                                .width(60),
+                                                .width(80),
                                             ]
                                             .width(Length::Fill)
                                             .spacing(20),
@@ -668,60 +1743,836 @@ impl List {
         .style(style::Container::Background)
         .into()
     }
+    fn recommend_view(&self) -> Element<Message, Renderer<Theme>> {
+        let title_ctn = container(
+            row![text(tr("Recommended for this device")).size(25)].align_items(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .style(style::Container::Frame)
+        .padding([10, 0, 10, 0])
+        .center_y()
+        .center_x();
+
+        let profile_text = match &self.device_profile {
+            None => text(tr("Reading the device properties. Please wait...")),
+            Some(profile) => text(
+                tr("Brand: {} | Carrier: {} | Android SDK: {}")
+                    .replacen(
+                        "{}",
+                        &if profile.brand.is_empty() {
+                            tr("unknown")
+                        } else {
+                            profile.brand.clone()
+                        },
+                        1,
+                    )
+                    .replacen(
+                        "{}",
+                        &if profile.carriers.is_empty() {
+                            tr("none detected")
+                        } else {
+                            profile.carriers.join(", ")
+                        },
+                        1,
+                    )
+                    .replacen("{}", &profile.android_sdk.to_string(), 1),
+            ),
+        };
+
+        let explanation = text(tr(
+            "Only the enabled packages marked as \"Recommended\" are selected: \
+            Google apps and the bloatware of your manufacturer and carrier. \
+            Review the selection before applying it.",
+        ))
+        .style(style::Text::Commentary);
+
+        let recommendations_list = if self.device_profile.is_some()
+            && self.recommendations.is_empty()
+        {
+            column![text(tr("Nothing to recommend"))]
+                .align_items(Alignment::Center)
+                .width(Length::Fill)
+        } else {
+            self.recommendations
+                .iter()
+                .fold(column![].spacing(6).width(Length::Fill), |col, r| {
+                    col.push(
+                        row![
+                            text(&r.name),
+                            horizontal_space(Length::Fill),
+                            text(&r.reason).style(style::Text::Commentary),
+                        ]
+                        .spacing(10)
+                        .align_items(Alignment::Center),
+                    )
+                })
+        };
+
+        let recommendations_ctn = container(
+            scrollable(
+                container(recommendations_list)
+                    .padding(10)
+                    .width(Length::Fill),
+            )
+            .style(style::Scrollable::Description),
+        )
+        .width(Length::Fill)
+        .max_height(450)
+        .style(style::Container::Frame);
+
+        let select_btn = button(text(tr("Select ({})").replacen(
+            "{}",
+            &self.recommendations.len().to_string(),
+            1,
+        )));
+        let select_btn = if self.recommendations.is_empty() {
+            select_btn
+        } else {
+            select_btn
+                .on_press(Message::SelectRecommendations)
+                .style(style::Button::Primary)
+        };
+
+        let modal_btn_row = row![
+            button(text(tr("Cancel"))).on_press(Message::ModalHide),
+            horizontal_space(Length::Fill),
+            select_btn,
+        ]
+        .padding([0, 15, 10, 10]);
+
+        container(
+            column![
+                title_ctn,
+                row![profile_text].padding([0, 10, 0, 10]),
+                row![explanation].padding([0, 10, 0, 10]),
+                row![recommendations_ctn].padding([0, 10, 0, 10]),
+                modal_btn_row,
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+        )
+        .width(900)
+        .height(Length::Shrink)
+        .max_height(700)
+        .style(style::Container::Background)
+        .into()
+    }
+
+    fn contribute_view(&self) -> Element<Message, Renderer<Theme>> {
+        let title_ctn = container(
+            row![text(tr("Contribute to the debloat list")).size(25)]
+                .align_items(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .style(style::Container::Frame)
+        .padding([10, 0, 10, 0])
+        .center_y()
+        .center_x();
+
+        let explanation = text(tr(
+            "Describe the installed packages that are not in the debloat list. \
+            Only the packages with a description are exported. \
+            The exported file can be submitted in a pull request or an issue on GitHub.",
+        ))
+        .style(style::Text::Commentary);
+
+        let contributions_list = match &self.contributions {
+            None => column![text(tr("Pulling packages from the device. Please wait..."))]
+                .align_items(Alignment::Center)
+                .width(Length::Fill),
+            Some(contributions) if contributions.is_empty() => column![text(tr(
+                "All the installed packages are already in the list"
+            ))]
+            .align_items(Alignment::Center)
+            .width(Length::Fill),
+            Some(contributions) => contributions.iter().enumerate().fold(
+                column![].spacing(15).width(Length::Fill),
+                |col, (i, c)| {
+                    col.push(
+                        column![
+                            row![
+                                text(&c.id),
+                                text(c.apk_name()).style(style::Text::Commentary),
+                                horizontal_space(Length::Fill),
+                                text(&c.version).style(style::Text::Commentary),
+                            ]
+                            .spacing(10),
+                            row![
+                                text(&c.apk_path).size(15).style(style::Text::Commentary),
+                                horizontal_space(Length::Fill),
+                                text(if c.installer.is_empty() {
+                                    tr("Preinstalled")
+                                } else {
+                                    tr("Installed by {}").replacen("{}", &c.installer, 1)
+                                })
+                                .size(15)
+                                .style(style::Text::Commentary),
+                            ],
+                            row![
+                                text_input(&tr("Description"), &c.description, move |d| {
+                                    Message::ContributionDescription(i, d)
+                                })
+                                .padding(5),
+                                pick_list(&UadList::ALL[1..7], Some(c.list), move |l| {
+                                    Message::ContributionList(i, l)
+                                }),
+                                pick_list(&Removal::ALL[1..5], Some(c.removal), move |r| {
+                                    Message::ContributionRemoval(i, r)
+                                }),
+                            ]
+                            .spacing(10)
+                            .align_items(Alignment::Center),
+                        ]
+                        .spacing(6),
+                    )
+                },
+            ),
+        };
+
+        let contributions_ctn = container(
+            scrollable(
+                container(contributions_list)
+                    .padding(10)
+                    .width(Length::Fill),
+            )
+            .style(style::Scrollable::Description),
+        )
+        .width(Length::Fill)
+        .max_height(450)
+        .style(style::Container::Frame);
+
+        let export_text = match &self.contributions_export {
+            Some(Ok(path)) => {
+                text(tr("Exported to {}").replacen("{}", &path.display().to_string(), 1))
+                    .style(style::Text::Ok)
+            }
+            Some(Err(e)) => {
+                text(tr("Export failed: {}").replacen("{}", e, 1)).style(style::Text::Danger)
+            }
+            None => text(""),
+        };
+
+        let nb_described = self.contributions.as_ref().map_or(0, |c| {
+            c.iter()
+                .filter(|c| !c.description.trim().is_empty())
+                .count()
+        });
+
+        let export_btn = button(text(tr("Export ({})").replacen(
+            "{}",
+            &nb_described.to_string(),
+            1,
+        )));
+        let export_btn = if nb_described == 0 {
+            export_btn
+        } else {
+            export_btn
+                .on_press(Message::ExportContributions)
+                .style(style::Button::Primary)
+        };
+
+        let modal_btn_row = row![
+            export_text,
+            horizontal_space(Length::Fill),
+            button(text(tr("Open directory")))
+                .on_press(Message::OpenContributionsDir)
+                .style(style::Button::Primary),
+            export_btn,
+            button(text(tr("Close"))).on_press(Message::ModalHide),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center)
+        .padding([0, 15, 10, 10]);
+
+        container(
+            column![
+                title_ctn,
+                row![explanation].padding([0, 10, 0, 10]),
+                row![contributions_ctn].padding([0, 10, 0, 10]),
+                modal_btn_row,
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+        )
+        .width(900)
+        .height(Length::Shrink)
+        .max_height(700)
+        .style(style::Container::Background)
+        .into()
+    }
+
+    fn list_changes_view(&self) -> Element<Message, Renderer<Theme>> {
+        let installed: HashSet<&str> = self
+            .phone_packages
+            .iter()
+            .flatten()
+            .filter(|p| p.state != PackageState::Uninstalled)
+            .map(|p| p.name.as_str())
+            .collect();
+
+        let title_ctn = container(
+            row![text(tr("Changes since the last update of the list")).size(25)]
+                .align_items(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .style(style::Container::Frame)
+        .padding([10, 0, 10, 0])
+        .center_y()
+        .center_x();
+
+        // Changes of the installed packages are listed first
+        let (on_device, others): (Vec<&ListChange>, Vec<&ListChange>) = self
+            .list_changes
+            .iter()
+            .partition(|c| installed.contains(c.id()));
+
+        let changes_list = on_device.iter().chain(others.iter()).fold(
+            column![].spacing(6).width(Length::Fill),
+            |col, change| {
+                let is_installed = installed.contains(change.id());
+                col.push(
+                    row![
+                        text(change.id()).style(if is_installed {
+                            style::Text::Default
+                        } else {
+                            style::Text::Commentary
+                        }),
+                        if is_installed {
+                            text(tr("installed")).style(style::Text::Danger)
+                        } else {
+                            text("")
+                        },
+                        horizontal_space(Length::Fill),
+                        text(change.to_string()),
+                    ]
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+                )
+            },
+        );
+
+        let changes_ctn = container(
+            scrollable(container(changes_list).padding(10).width(Length::Fill))
+                .style(style::Scrollable::Description),
+        )
+        .width(Length::Fill)
+        .max_height(450)
+        .style(style::Container::Frame);
+
+        let modal_btn_row = row![
+            text(tr("{} change(s) affecting this device").replacen(
+                "{}",
+                &on_device.len().to_string(),
+                1
+            ))
+            .style(style::Text::Commentary),
+            horizontal_space(Length::Fill),
+            button(text(tr("Close"))).on_press(Message::ModalHide),
+        ]
+        .align_items(Alignment::Center)
+        .padding([0, 15, 10, 10]);
+
+        container(
+            column![
+                title_ctn,
+                row![changes_ctn].padding([0, 10, 0, 10]),
+                modal_btn_row,
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+        )
+        .width(800)
+        .height(Length::Shrink)
+        .max_height(700)
+        .style(style::Container::Background)
+        .into()
+    }
+
+    fn package_components_modal(
+        &self,
+        settings: &Settings,
+        package: &PackageRow,
+    ) -> Element<Message, Renderer<Theme>> {
+        // Same restriction as for the package itself. The shell user can't change components.
+        let locked = (package.removal == Removal::Unsafe && !settings.general.expert_mode)
+            || !settings.device.root_mode;
+
+        let title_ctn = container(
+            row![text(tr("Components of {}").replacen("{}", &package.name, 1)).size(25)]
+                .align_items(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .style(style::Container::Frame)
+        .padding([10, 0, 10, 0])
+        .center_y()
+        .center_x();
+
+        let warning_ctn = container(
+            text(tr(
+                "Disabling a component can break its app (or the system). \
+                Only disable the trackers and services you know.",
+            ))
+            .style(style::Text::Danger),
+        )
+        .center_x()
+        .padding(10)
+        .style(style::Container::BorderedFrame);
+
+        let root_text = if settings.device.root_mode {
+            text("")
+        } else {
+            text(tr(
+                "Changing components needs root: enable the root mode in the settings (expert mode)",
+            ))
+            .style(style::Text::Commentary)
+        };
+
+        let components_list = match &self.components {
+            None => column![text(tr(
+                "Pulling components from the device. Please wait..."
+            ))]
+            .align_items(Alignment::Center)
+            .width(Length::Fill),
+            Some(components) if components.is_empty() => column![text(tr("No component found"))]
+                .align_items(Alignment::Center)
+                .width(Length::Fill),
+            Some(components) => {
+                components
+                    .iter()
+                    .fold(column![].spacing(6).width(Length::Fill), |col, c| {
+                        let name = c.name.clone();
+                        col.push(
+                            row![
+                                checkbox(c.name.clone(), c.enabled, move |toggle| {
+                                    if locked {
+                                        Message::Nothing
+                                    } else {
+                                        Message::ToggleComponent(name.clone(), toggle)
+                                    }
+                                })
+                                .style(if locked {
+                                    style::CheckBox::PackageDisabled
+                                } else {
+                                    style::CheckBox::PackageEnabled
+                                }),
+                                horizontal_space(Length::Fill),
+                                text(c.kind).style(style::Text::Commentary),
+                            ]
+                            .spacing(20)
+                            .align_items(Alignment::Center),
+                        )
+                    })
+            }
+        };
+
+        let components_ctn = container(
+            scrollable(container(components_list).padding(10).width(Length::Fill))
+                .style(style::Scrollable::Description),
+        )
+        .width(Length::Fill)
+        .max_height(450)
+        .style(style::Container::Frame);
+
+        let modal_btn_row = row![
+            horizontal_space(Length::Fill),
+            button(text(tr("Close"))).on_press(Message::ModalHide),
+        ]
+        .padding([0, 15, 10, 10]);
+
+        container(
+            column![
+                title_ctn,
+                row![warning_ctn].padding([0, 10, 0, 10]),
+                row![root_text].padding([0, 10, 0, 10]),
+                row![components_ctn].padding([0, 10, 0, 10]),
+                modal_btn_row,
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center),
+        )
+        .width(800)
+        .height(Length::Shrink)
+        .max_height(700)
+        .style(style::Container::Background)
+        .into()
+    }
+
+    // Android only refreshes the package sizes once a day so they can't be measured
+    // again right after the batch. The space freed by each removal/list is estimated from
+    // the sizes measured before the batch and the actual state of each package after it.
+    // The total is the growth of the free space of the data partition when it was measured
+    // before the batch.
+    fn compute_batch_report(&self, batch: &Batch, free_space: Option<u64>) -> BatchReport {
+        let mut report = BatchReport::default();
+        let mut counted = HashSet::new();
+
+        for &(i_user, index) in &batch.changed {
+            let package = &self.phone_packages[i_user][index];
+            if package.state != PackageState::Uninstalled && package.state != PackageState::Disabled
+            {
+                continue;
+            }
+            report.nb_removed += 1;
+
+            // Sizes are the total of all users
+            if counted.insert(package.name.clone()) {
+                let freed = batch
+                    .sizes
+                    .get(&package.name)
+                    .map_or(0, |s| s.data + s.cache);
+                report.freed += freed;
+                *report.freed_by_removal.entry(package.removal).or_default() += freed;
+                *report.freed_by_list.entry(package.uad_list).or_default() += freed;
+            }
+        }
+        if let (Some(before), Some(after)) = (batch.free_space, free_space) {
+            report.freed = after.saturating_sub(before);
+            report.measured = true;
+        }
+        report
+    }
+
+    // Verifies the batch once its last job is done
+    fn batch_jobs_done(&mut self, nb: usize, selected_device: &Phone) -> Command<Message> {
+        let Some(batch) = self.batch.as_mut() else {
+            return Command::none();
+        };
+        batch.pending = batch.pending.saturating_sub(nb);
+        if batch.pending > 0 {
+            return Command::none();
+        }
+        // The output of adb commands can't be trusted
+        let mut users: Vec<usize> = batch.targets.iter().map(|t| t.0).collect();
+        users.sort_unstable();
+        users.dedup();
+        // Packages are loaded without user on single-user devices
+        let users = users
+            .into_iter()
+            .map(|i| {
+                let user = selected_device
+                    .user_list
+                    .iter()
+                    .find(|u| u.index == i)
+                    .filter(|_| selected_device.user_list.len() > 1)
+                    .cloned();
+                (i, user)
+            })
+            .collect();
+        Command::perform(query_batch_result(users), |(states, free_space)| {
+            Message::BatchVerified(states, free_space)
+        })
+    }
+
+    /// Gives packages (user index, package index) a state, in a batch checked and reported
+    /// like the one of the review modal. Unsafe (without expert mode) and boot-critical
+    /// packages are not removed.
+    fn start_state_batch(
+        &mut self,
+        wanted: &[(usize, usize, PackageState)],
+        selected_device: &Phone,
+        settings: &Settings,
+        queue: &mut Queue,
+    ) {
+        let mut batch = self.batch.take().unwrap_or_else(|| Batch {
+            sizes: self.packages_size.clone(),
+            ..Batch::default()
+        });
+        let mut jobs = vec![];
+        for &(i_user, index, state) in wanted {
+            // Same locks as the package list: these removals need the review modal
+            let package = &self.phone_packages[i_user][index];
+            if state != PackageState::Enabled
+                && (package.boot_critical
+                    || (package.removal == Removal::Unsafe && !settings.general.expert_mode))
+            {
+                warn!(
+                    "[BATCH] {} skipped: remove it from the package list",
+                    package.name
+                );
+                continue;
+            }
+            if let Some(job) = build_state_job(
+                &self.phone_packages,
+                selected_device,
+                &settings.device,
+                (i_user, index),
+                state,
+            ) {
+                jobs.push(job);
+                batch.pending += 1;
+                batch.targets.push((i_user, index, state));
+            }
+        }
+        if batch.pending > 0 {
+            self.batch = Some(batch);
+        }
+        queue.push(jobs);
+    }
+
+    // States of the packages of a user (by user index)
+    fn user_states(&self, i_user: usize) -> States {
+        self.phone_packages[i_user]
+            .iter()
+            .map(|p| (p.name.as_str(), p.state))
+            .collect()
+    }
+
+    /// (target, package index, state) giving the packages of the `target` user the state they
+    /// have for the `reference` user (user indexes). Only `names` are aligned if given.
+    fn user_alignment(
+        &self,
+        reference: usize,
+        target: usize,
+        names: Option<&HashSet<&str>>,
+    ) -> Vec<(usize, usize, PackageState)> {
+        let indexes: HashMap<&str, usize> = self.phone_packages[target]
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p.name.as_str(), i))
+            .collect();
+        alignment(&self.user_states(reference), &self.user_states(target))
+            .into_iter()
+            .filter(|(name, _)| names.is_none_or(|n| n.contains(name)))
+            .filter_map(|(name, state)| Some((target, *indexes.get(name)?, state)))
+            .collect()
+    }
+
+    // Packages of the matrix: the ones shown for the selected user, with the matrix filter
+    fn matrix_names(&self, columns: &[States]) -> Vec<&str> {
+        let i_user = self.selected_user.as_ref().map_or(0, |u| u.index);
+        self.filtered_packages
+            .iter()
+            .map(|&i| self.phone_packages[i_user][i].name.as_str())
+            .filter(|name| !self.matrix_differs || differs(name, columns))
+            .collect()
+    }
+
+    fn user_matrix_view(&self, selected_device: &Phone) -> Element<Message, Renderer<Theme>> {
+        let users = matrix_users(selected_device);
+        let reference = self
+            .matrix_reference
+            .clone()
+            .or_else(|| users.first().map(|&u| u.clone()));
+        let i_ref = users
+            .iter()
+            .position(|u| Some(u.index) == reference.as_ref().map(|r| r.index))
+            .unwrap_or_default();
+        let columns: Vec<States> = users.iter().map(|u| self.user_states(u.index)).collect();
+        let names = self.matrix_names(&columns);
+
+        let header = users.iter().fold(
+            row![text(tr("Package")).width(Length::FillPortion(3))].spacing(10),
+            |row, user| row.push(text(user.to_string()).width(Length::FillPortion(2))),
+        );
+
+        // States different from the one of the reference user stand out
+        let rows = names.iter().fold(column![].spacing(6), |col, &name| {
+            col.push(columns.iter().fold(
+                row![text(name).width(Length::FillPortion(3))].spacing(10),
+                |row, states| {
+                    let state = states.get(name);
+                    let cell = text(state.map_or_else(|| "-".to_string(), ToString::to_string))
+                        .width(Length::FillPortion(2));
+                    row.push(if state == columns[i_ref].get(name) {
+                        cell
+                    } else {
+                        cell.style(style::Text::Danger)
+                    })
+                },
+            ))
+        });
+
+        let differs_checkbox = checkbox(
+            tr("Only packages that differ between users"),
+            self.matrix_differs,
+            Message::MatrixDiffersToggled,
+        )
+        .style(style::CheckBox::SettingsEnabled);
+
+        let reference_picklist = pick_list(
+            users.iter().map(|&u| u.clone()).collect::<Vec<User>>(),
+            reference,
+            Message::MatrixReferenceSelected,
+        )
+        .width(220);
+
+        let align_btn = button(text(tr("Align the shown packages of the other users on")))
+            .padding(5)
+            .on_press(Message::AlignUsers)
+            .style(style::Button::Primary);
+
+        let controls = row![
+            differs_checkbox,
+            Space::new(Length::Fill, Length::Shrink),
+            align_btn,
+            reference_picklist,
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        container(
+            column![
+                controls,
+                container(header).padding([0, 15, 0, 10]),
+                scrollable(container(rows).padding([0, 15, 0, 10]))
+                    .height(Length::Fill)
+                    .style(style::Scrollable::Packages),
+            ]
+            .spacing(10),
+        )
+        .height(Length::FillPortion(6))
+        .into()
+    }
+
+    /// Failures are the ones of the last batch
+    fn clear_failures(&mut self) {
+        self.phone_packages
+            .iter_mut()
+            .flatten()
+            .for_each(|p| p.error = None);
+        self.failed_filter = false;
+        Self::filter_package_lists(self);
+    }
+
+    // Names of the selected boot-critical packages about to be removed
+    fn boot_critical_selection(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .selected_packages
+            .iter()
+            .map(|&(u, i)| &self.phone_packages[u][i])
+            .filter(|p| p.boot_critical && p.state == PackageState::Enabled)
+            .map(|p| p.name.as_str())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    // Saves a script restoring the packages removed by the batch then reboots the device
+    fn start_trial(&mut self, batch: &Batch, device: &Phone, root: bool) -> Command<Message> {
+        let commands: Vec<String> = batch
+            .changed
+            .iter()
+            .filter_map(|&(i_user, index)| {
+                let package = &self.phone_packages[i_user][index];
+                let user = device.user_list.iter().find(|u| u.index == i_user)?;
+                (package.state != PackageState::Enabled).then(|| {
+                    apply_pkg_state_commands(
+                        &package.into(),
+                        PackageState::Enabled,
+                        user,
+                        device,
+                        root,
+                    )
+                })
+            })
+            .flatten()
+            .collect();
+        if commands.is_empty() {
+            return Command::none();
+        }
+
+        match write_recovery_script(&device.adb_id, &commands) {
+            Ok(script) => {
+                self.trial = Some(Trial::Rebooting(script));
+                Command::perform(reboot_and_wait(), Message::TrialFinished)
+            }
+            Err(e) => {
+                error!("[TRIAL] The device won't be rebooted: {}", e);
+                Command::none()
+            }
+        }
+    }
+
+    // Applies the debloat lists to the packages already loaded
+    fn update_package_descriptions(&mut self) {
+        let no_description = tr("[No description] : CONTRIBUTION WELCOMED");
+        for package in self.phone_packages.iter_mut().flatten() {
+            package.boot_critical =
+                is_boot_critical(&package.name, self.uad_lists.get(&package.name));
+            match self.uad_lists.get(&package.name) {
+                Some(p) => {
+                    package.description = if p.description.is_empty() {
+                        no_description.clone()
+                    } else {
+                        p.localized_description().to_string()
+                    };
+                    package.uad_list = p.list;
+                    package.removal = p.removal;
+                    package.source = p.source.clone();
+                }
+                None => {
+                    package.description = no_description.clone();
+                    package.uad_list = UadList::Unlisted;
+                    package.removal = Removal::Unlisted;
+                    package.source = None;
+                }
+            }
+        }
+    }
+
     fn filter_package_lists(&mut self) {
-        let list_filter: UadList = self.selected_list.unwrap();
-        let package_filter: PackageState = self.selected_package_state.unwrap();
-        let removal_filter: Removal = self.selected_removal.unwrap();
+        let query = parse_query(&self.input_value);
+        // Filters typed in the search bar override the pick lists
+        let list_filter: UadList = query.list.unwrap_or(self.selected_list.unwrap());
+        let package_filter: PackageState =
+            query.state.unwrap_or(self.selected_package_state.unwrap());
+        let network_filter: NetworkState = query
+            .network
+            .unwrap_or(self.selected_network_state.unwrap());
+        let removal_filter: Removal = query.removal.unwrap_or(self.selected_removal.unwrap());
 
-        self.filtered_packages = self.phone_packages[self.selected_user.unwrap().index]
+        let mut matches: Vec<(usize, u32)> = self.phone_packages
+            [self.selected_user.as_ref().unwrap().index]
             .iter()
             .enumerate()
             .filter(|(_, p)| {
                 (list_filter == UadList::All || p.uad_list == list_filter)
                     && (package_filter == PackageState::All || p.state == package_filter)
+                    && (network_filter == NetworkState::All || p.network == network_filter)
                     && (removal_filter == Removal::All || p.removal == removal_filter)
-                    && (self.input_value.is_empty() || p.name.contains(&self.input_value))
+                    && (!self.failed_filter || p.error.is_some())
             })
-            .map(|(i, _)| i)
+            .filter_map(|(i, p)| Some((i, query.score(p)?)))
             .collect();
+
+        // Best matches first. Without search terms, packages stay sorted by name.
+        if !query.terms.is_empty() {
+            matches.sort_by(|a, b| b.1.cmp(&a.1));
+        }
+        self.filtered_packages = matches.into_iter().map(|(i, _)| i).collect();
     }
 
     async fn load_packages(
         uad_list: HashMap<String, Package>,
         user_list: Vec<User>,
+        android_sdk: u8,
     ) -> Vec<Vec<PackageRow>> {
         let mut phone_packages = vec![];
 
         if user_list.len() <= 1 {
-            phone_packages.push(fetch_packages(&uad_list, None));
+            phone_packages.push(fetch_packages(&uad_list, None, android_sdk));
         } else {
             phone_packages.extend(
                 user_list
                     .iter()
-                    .map(|user| fetch_packages(&uad_list, Some(user))),
+                    .map(|user| fetch_packages(&uad_list, Some(user), android_sdk)),
             );
         };
         phone_packages
     }
 
-    async fn init_apps_view(
-        remote: bool,
-        phone: Phone,
-    ) -> (HashMap<String, Package>, UadListState) {
-        let (uad_lists, _) = load_debloat_lists(remote);
-        match uad_lists {
-            Ok(list) => {
-                env::set_var("ANDROID_SERIAL", phone.adb_id.clone());
-                if phone.adb_id.is_empty() {
-                    error!("AppsView ready but no phone found");
-                }
-                (list, UadListState::Done)
-            }
-            Err(local_list) => {
-                error!("Error loading remote debloat list for the phone. Fallback to embedded (and outdated) list");
-                (local_list, UadListState::Failed)
+    async fn init_apps_view(remote: bool, sources: Vec<ListSource>, phone: Phone) -> ListBox {
+        let (list, state, list_error) = load_debloat_lists(remote, &sources);
+        if state == UadListState::Done {
+            env::set_var("ANDROID_SERIAL", phone.adb_id.clone());
+            if phone.adb_id.is_empty() {
+                error!("AppsView ready but no phone found");
             }
+        } else {
+            error!("Error loading remote debloat list for the phone. Fallback to embedded (and outdated) list");
         }
+        (list, state, list_error)
     }
 }
 
@@ -731,7 +2582,7 @@ fn waiting_view<'a>(
     btn: bool,
 ) -> Element<'a, Message, Renderer<Theme>> {
     let col = if btn {
-        let no_internet_btn = button("No internet?")
+        let no_internet_btn = button(text(tr("No internet?")))
             .padding(5)
             .on_press(Message::LoadUadList(false))
             .style(style::Button::Primary);
@@ -757,50 +2608,160 @@ fn waiting_view<'a>(
         .into()
 }
 
-fn build_action_pkg_commands(
+fn build_action_pkg_jobs(
     packages: &[Vec<PackageRow>],
     device: &Phone,
     settings: &DeviceSettings,
     selection: (usize, usize),
-) -> Vec<Command<Message>> {
+) -> (Vec<Job>, Vec<(usize, usize, PackageState)>) {
     let pkg = &packages[selection.0][selection.1];
-    let wanted_state = pkg.state.opposite(settings.disable_mode);
+    let wanted_state = pkg.state.opposite(settings.removal_state());
 
-    let mut commands = vec![];
-    for u in device.user_list.iter().filter(|&&u| {
+    let mut jobs = vec![];
+    let mut targets = vec![];
+    for u in device.user_list.iter().filter(|u| {
         !u.protected && (packages[u.index][selection.1].selected || settings.multi_user_mode)
     }) {
-        let u_pkg = packages[u.index][selection.1].clone();
-        let actions = if settings.multi_user_mode {
-            apply_pkg_state_commands(&u_pkg.into(), wanted_state, u, device)
+        let wanted_state = if settings.multi_user_mode {
+            wanted_state
         } else {
-            let wanted_state = u_pkg.state.opposite(settings.disable_mode);
-            apply_pkg_state_commands(&u_pkg.into(), wanted_state, u, device)
+            packages[u.index][selection.1]
+                .state
+                .opposite(settings.removal_state())
         };
-        for (j, action) in actions.into_iter().enumerate() {
+        if let Some(job) = build_state_job(
+            packages,
+            device,
+            settings,
+            (u.index, selection.1),
+            wanted_state,
+        ) {
+            targets.push((u.index, selection.1, wanted_state));
+            jobs.push(job);
+        }
+    }
+    (jobs, targets)
+}
+
+// Users shown side by side: protected ones have no packages
+fn matrix_users(device: &Phone) -> Vec<&User> {
+    device.user_list.iter().filter(|u| !u.protected).collect()
+}
+
+// Job giving a package (user index, package index) a state. `None` if there is nothing to do
+// or the user is protected.
+fn build_state_job(
+    packages: &[Vec<PackageRow>],
+    device: &Phone,
+    settings: &DeviceSettings,
+    (i_user, index): (usize, usize),
+    wanted_state: PackageState,
+) -> Option<Job> {
+    let user = device
+        .user_list
+        .iter()
+        .find(|u| u.index == i_user && !u.protected)?;
+    let pkg = &packages[i_user][index];
+    let actions =
+        apply_pkg_state_commands(&pkg.into(), wanted_state, user, device, settings.root_mode);
+    if actions.is_empty() {
+        return None;
+    }
+    let p_info = PackageInfo {
+        i_user,
+        index,
+        removal: pkg.removal.to_string(),
+    };
+    // In the end there is only one package state change
+    // even if we run multiple adb commands
+    Some(Job::new(
+        JobKind::PackageState,
+        actions,
+        CommandType::PackageManager(p_info),
+    ))
+}
+
+// Package sizes and free space of the data partition before a batch
+async fn measure_storage() -> (HashMap<String, PackageSize>, Option<u64>) {
+    (get_packages_size().await, get_data_free_space())
+}
+
+// Real state of the packages of the users (by user index) touched by a batch and
+// free space of the data partition after it
+async fn query_batch_result(
+    users: Vec<(usize, Option<User>)>,
+) -> (Vec<(usize, HashMap<String, PackageState>)>, Option<u64>) {
+    let states = users
+        .into_iter()
+        .map(|(i, user)| (i, get_packages_state(user.as_ref())))
+        .collect();
+    (states, get_data_free_space())
+}
+
+fn build_uninstall_updates_commands(
+    packages: &[Vec<PackageRow>],
+    device: &Phone,
+    selection: (usize, usize),
+) -> Vec<Command<Message>> {
+    let pkg = &packages[selection.0][selection.1];
+    let update_path = match &pkg.update_path {
+        Some(path) => path.clone(),
+        None => return vec![],
+    };
+
+    apply_uninstall_updates_commands(&pkg.into(), device)
+        .into_iter()
+        .map(|action| {
             let p_info = PackageInfo {
-                i_user: u.index,
+                i_user: selection.0,
                 index: selection.1,
                 removal: pkg.removal.to_string(),
             };
-            // In the end there is only one package state change
-            // even if we run multiple adb commands
-            commands.push(Command::perform(
-                perform_adb_commands(action, CommandType::PackageManager(p_info)),
-                if j == 0 {
-                    Message::ChangePackageState
-                } else {
-                    |_| Message::Nothing
-                },
-            ));
-        }
-    }
-    commands
+            Command::perform(
+                perform_uninstall_updates(
+                    action,
+                    update_path.clone(),
+                    CommandType::PackageManager(p_info),
+                ),
+                |(command_type, res)| Message::SystemUpdateRemoved(command_type, res),
+            )
+        })
+        .collect()
+}
+
+fn build_network_pkg_command(
+    packages: &[Vec<PackageRow>],
+    device: &Phone,
+    selection: (usize, usize),
+) -> Command<Message> {
+    let pkg = &packages[selection.0][selection.1];
+    let user = device
+        .user_list
+        .iter()
+        .find(|u| u.index == selection.0)
+        .cloned()
+        .unwrap_or_default();
+    let p_info = PackageInfo {
+        i_user: selection.0,
+        index: selection.1,
+        removal: pkg.removal.to_string(),
+    };
+    Command::perform(
+        perform_network_commands(
+            pkg.into(),
+            pkg.network.toggle(),
+            user,
+            device.clone(),
+            CommandType::PackageManager(p_info),
+        ),
+        |(command_type, state, res)| Message::ChangeNetworkState(command_type, state, res),
+    )
 }
 
 fn recap<'a>(
     settings: &Settings,
     recap: &mut HashMap<Removal, (u8, u8)>,
+    freed: u64,
     removal: Removal,
 ) -> Element<'a, Message, Renderer<Theme>> {
     container(
@@ -808,11 +2769,18 @@ fn recap<'a>(
             text(removal).size(25).width(Length::FillPortion(1)),
             vertical_rule(5),
             row![
-                if settings.device.disable_mode {
-                    text("Disable").style(style::Text::Danger)
-                } else {
-                    text("Uninstall").style(style::Text::Danger)
-                },
+                text(tr("Space freed")),
+                horizontal_space(Length::Fill),
+                text(format!("~{}", format_size(freed))).style(style::Text::Commentary)
+            ]
+            .width(Length::FillPortion(1)),
+            vertical_rule(5),
+            row![
+                text(tr(action_text_for(
+                    PackageState::Enabled,
+                    settings.device.removal_state()
+                )))
+                .style(style::Text::Danger),
                 horizontal_space(Length::Fill),
                 text(recap.entry(removal).or_insert((0, 0)).0.to_string())
                     .style(style::Text::Danger)
@@ -821,9 +2789,9 @@ fn recap<'a>(
             vertical_rule(5),
             row![
                 if settings.device.disable_mode {
-                    text("Enable").style(style::Text::Ok)
+                    text(tr("Enable")).style(style::Text::Ok)
                 } else {
-                    text("Restore").style(style::Text::Ok)
+                    text(tr("Restore")).style(style::Text::Ok)
                 },
                 horizontal_space(Length::Fill),
                 text(recap.entry(removal).or_insert((0, 0)).1.to_string()).style(style::Text::Ok)
@@ -841,3 +2809,245 @@ fn recap<'a>(
     .style(style::Container::Frame)
     .into()
 }
+
+fn log_batch_report(report: &BatchReport) {
+    info!(
+        "[BATCH] {} package(s) removed: {} freed, {} mismatch(es), {} error(s)",
+        report.nb_removed,
+        format_size(report.freed),
+        report.mismatches.len(),
+        report.errors.iter().map(|(_, nb)| nb).sum::<usize>()
+    );
+    for (removal, freed) in &report.freed_by_removal {
+        info!("[BATCH] {} -> {}", removal, format_size(*freed));
+    }
+    for (list, freed) in &report.freed_by_list {
+        info!("[BATCH] {} -> {}", list, format_size(*freed));
+    }
+}
+
+fn batch_report_modal<'a>(report: &BatchReport) -> Element<'a, Message, Renderer<Theme>> {
+    let title_ctn =
+        container(row![text(tr("Batch report")).size(25)].align_items(Alignment::Center))
+            .width(Length::Fill)
+            .style(style::Container::Frame)
+            .padding([10, 0, 10, 0])
+            .center_y()
+            .center_x();
+
+    let total_ctn = container(text(
+        tr("{} package(s) removed: {} freed")
+            .replacen("{}", &report.nb_removed.to_string(), 1)
+            .replacen(
+                "{}",
+                &format!(
+                    "{}{}",
+                    if report.measured { "" } else { "~" },
+                    format_size(report.freed)
+                ),
+                1,
+            ),
+    ))
+    .padding(10)
+    .center_x()
+    .style(style::Container::Frame);
+
+    let freed_row = |label: String, freed: u64| {
+        container(
+            row![
+                text(label),
+                horizontal_space(Length::Fill),
+                text(format_size(freed)).style(style::Text::Ok)
+            ]
+            .width(Length::Fill),
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .style(style::Container::Frame)
+    };
+
+    let by_removal = Removal::ALL
+        .iter()
+        .filter_map(|r| Some((r, *report.freed_by_removal.get(r)?)))
+        .fold(
+            column![].spacing(6).width(Length::Fill),
+            |col, (r, freed)| col.push(freed_row(r.to_string(), freed)),
+        );
+
+    let by_list = UadList::ALL
+        .iter()
+        .filter_map(|l| Some((l, *report.freed_by_list.get(l)?)))
+        .fold(
+            column![].spacing(6).width(Length::Fill),
+            |col, (l, freed)| col.push(freed_row(l.to_string(), freed)),
+        );
+
+    let explanation = text(tr(
+        "Only the data of uninstalled and disabled packages is freed. \
+        The APKs of system apps stay on the read-only system partition.",
+    ))
+    .size(15)
+    .style(style::Text::Commentary);
+
+    let mismatches_ctn = if report.mismatches.is_empty() {
+        column![]
+    } else {
+        column![
+            text(
+                tr("{} package(s) are not in the expected state on the device:").replacen(
+                    "{}",
+                    &report.mismatches.len().to_string(),
+                    1
+                )
+            )
+            .style(style::Text::Danger),
+            scrollable(
+                report
+                    .mismatches
+                    .iter()
+                    .fold(column![].spacing(4).width(Length::Fill), |col, m| {
+                        col.push(text(m).size(15))
+                    })
+            )
+            .height(Length::Shrink)
+            .style(style::Scrollable::Description),
+        ]
+        .spacing(6)
+        .padding([0, 10, 0, 10])
+    };
+
+    let errors_ctn = if report.errors.is_empty() {
+        column![]
+    } else {
+        column![
+            text(tr("The device refused some changes:")).style(style::Text::Danger),
+            report
+                .errors
+                .iter()
+                .fold(column![].spacing(4).width(Length::Fill), |col, (m, nb)| {
+                    col.push(
+                        text(
+                            tr("{} package(s): {}")
+                                .replacen("{}", &nb.to_string(), 1)
+                                .replacen("{}", m, 1),
+                        )
+                        .size(15),
+                    )
+                })
+        ]
+        .spacing(6)
+        .padding([0, 10, 0, 10])
+    };
+
+    let modal_btn_row = row![
+        horizontal_space(Length::Fill),
+        button(text(tr("Close"))).on_press(Message::ModalHide),
+    ]
+    .padding([0, 15, 10, 10]);
+
+    container(
+        column![
+            title_ctn,
+            total_ctn,
+            row![
+                column![text(tr("Per removal level")), by_removal].spacing(6),
+                column![text(tr("Per list")), by_list].spacing(6),
+            ]
+            .spacing(20)
+            .padding([0, 10, 0, 10]),
+            mismatches_ctn,
+            errors_ctn,
+            explanation,
+            modal_btn_row,
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+    )
+    .width(800)
+    .height(Length::Shrink)
+    .max_height(700)
+    .style(style::Container::Background)
+    .into()
+}
+
+fn trial_view<'a>(trial: &Trial) -> Element<'a, Message, Renderer<Theme>> {
+    let title_ctn = container(row![text(tr("Trial mode")).size(25)].align_items(Alignment::Center))
+        .width(Length::Fill)
+        .style(style::Container::Frame)
+        .padding([10, 0, 10, 0])
+        .center_y()
+        .center_x();
+
+    let script_text = |script: &PathBuf| {
+        text(tr("Recovery script: {}").replacen("{}", &script.display().to_string(), 1))
+            .style(style::Text::Commentary)
+    };
+
+    let (content, closable) = match trial {
+        Trial::Rebooting(script) => (
+            column![
+                text(tr(
+                    "Rebooting the device and waiting for it to boot (up to 5 minutes). \
+                    Don't unplug it."
+                )),
+                script_text(script),
+            ],
+            false,
+        ),
+        Trial::Booted(duration) => (
+            column![text(
+                tr("The device booted successfully in {} seconds.").replacen(
+                    "{}",
+                    &duration.to_string(),
+                    1
+                )
+            )
+            .style(style::Text::Ok)],
+            true,
+        ),
+        Trial::Failed(script, e) => (
+            column![
+                text(format!("{e}!")).style(style::Text::Danger),
+                text(tr(
+                    "1. Wait a few more minutes: the first boot after removing packages can be \
+                    slow.\n\
+                    2. As soon as `adb devices` lists the device (it often does while \
+                    bootlooping), run the recovery script. It reinstalls the packages removed \
+                    by the last batch.\n\
+                    3. If adb never sees the device, boot it into safe mode (see the manual of \
+                    your device) and try again.\n\
+                    4. As a last resort, a factory reset from the recovery menu restores all \
+                    the packages but erases your data."
+                )),
+                script_text(script),
+            ],
+            true,
+        ),
+    };
+
+    let modal_btn_row = row![
+        button(text(tr("Open recovery directory"))).on_press(Message::OpenRecoveryDir),
+        horizontal_space(Length::Fill),
+    ]
+    .padding([0, 15, 10, 10]);
+    let modal_btn_row = if closable {
+        modal_btn_row.push(button(text(tr("Close"))).on_press(Message::ModalHide))
+    } else {
+        modal_btn_row
+    };
+
+    container(
+        column![
+            title_ctn,
+            container(content.spacing(10)).padding(10),
+            modal_btn_row,
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center),
+    )
+    .width(800)
+    .height(Length::Shrink)
+    .max_height(700)
+    .style(style::Container::Background)
+    .into()
+}
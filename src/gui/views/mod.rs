@@ -1,3 +1,4 @@
 pub mod about;
+pub mod compare;
 pub mod list;
 pub mod settings;
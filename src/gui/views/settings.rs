@@ -1,15 +1,22 @@
 use crate::core::config::{BackupSettings, Config, DeviceSettings, GeneralSettings};
+use crate::core::i18n::{current_language, set_language, tr, Language};
+use crate::core::queue::{Job, JobKind, Queue};
+use crate::core::root::detect_root;
 use crate::core::save::{
     backup_phone, list_available_backup_user, list_available_backups, restore_backup, BACKUP_DIR,
 };
-use crate::core::sync::{get_android_sdk, perform_adb_commands, CommandType, Phone};
+use crate::core::sync::{get_android_sdk, AdbError, CommandType, Phone, User};
 use crate::core::theme::Theme;
+use crate::core::uad_lists::ListSource;
+use crate::core::users::{create_user, manage_user, NewUserKind, UserAction};
 use crate::core::utils::{open_url, string_to_theme, DisplayablePath};
 use crate::gui::style;
 use crate::gui::views::list::PackageInfo;
 use crate::gui::widgets::package_row::PackageRow;
 
-use iced::widget::{button, checkbox, column, container, pick_list, radio, row, text, Space};
+use iced::widget::{
+    button, checkbox, column, container, pick_list, radio, row, text, text_input, Space,
+};
 use iced::{alignment, Alignment, Command, Element, Length, Renderer};
 use std::path::PathBuf;
 
@@ -17,6 +24,38 @@ use std::path::PathBuf;
 pub struct Settings {
     pub general: GeneralSettings,
     pub device: DeviceSettings,
+    pub new_list_source: ListSource,
+    root_denied: bool, // `su` wasn't found or was denied when enabling the root mode
+    new_user: NewUser,
+    remove_confirmation: Option<u16>, // user id
+    users_status: String,
+}
+
+// Form of the "Users" section
+#[derive(Default, Debug, Clone)]
+struct NewUser {
+    name: String,
+    kind: NewUserKind,
+    copy_states: bool,
+    states_source: Option<StatesSource>, // package states given to the new user
+}
+
+/// Profile applied to a new user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatesSource {
+    User(User), // current states of another user
+    Backup(DisplayablePath),
+    Recommendations,
+}
+
+impl std::fmt::Display for StatesSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::User(user) => write!(f, "{user}"),
+            Self::Backup(backup) => write!(f, "{} {backup}", tr("Backup")),
+            Self::Recommendations => write!(f, "{}", tr("Recommended for this device")),
+        }
+    }
 }
 
 impl Default for Settings {
@@ -24,6 +63,11 @@ impl Default for Settings {
         Self {
             general: Config::load_configuration_file().general,
             device: DeviceSettings::default(),
+            new_list_source: ListSource::default(),
+            root_denied: false,
+            new_user: NewUser::default(),
+            remove_confirmation: None,
+            users_status: String::new(),
         }
     }
 }
@@ -32,15 +76,33 @@ impl Default for Settings {
 pub enum Message {
     LoadDeviceSettings,
     ExpertMode(bool),
+    BackgroundListRefresh(bool),
     DisableMode(bool),
+    SuspendMode(bool),
     MultiUserMode(bool),
+    TrialMode(bool),
+    RootMode(bool),
+    RootDetected(bool),
     ApplyTheme(Theme),
+    LanguageSelected(Language),
+    ListSourceNameChanged(String),
+    ListSourceLocationChanged(String),
+    AddListSource,
+    RemoveListSource(usize),
     UrlPressed(PathBuf),
     BackupSelected(DisplayablePath),
     BackupDevice,
     RestoreDevice,
-    RestoringDevice(Result<CommandType, ()>),
+    RestoringDevice(Result<CommandType, AdbError>),
     DeviceBackedUp(Result<(), String>),
+    NewUserNameChanged(String),
+    NewUserKindSelected(NewUserKind),
+    CopyStatesToggled(bool),
+    StatesSourceSelected(StatesSource),
+    CreateUser,
+    UserCreated(Result<u16, AdbError>), // id of the new user
+    UserActionPressed(u16, UserAction),
+    UserActionDone(Result<(), AdbError>),
 }
 
 impl Settings {
@@ -48,19 +110,46 @@ impl Settings {
         &mut self,
         phone: &Phone,
         packages: &[Vec<PackageRow>],
-        nb_running_async_adb_commands: &mut u32,
+        queue: &mut Queue,
         msg: Message,
     ) -> Command<Message> {
         match msg {
             Message::ExpertMode(toggled) => {
                 self.general.expert_mode = toggled;
+                // Root actions are expert actions
+                if !toggled {
+                    self.device.root_mode = false;
+                    if phone.android_sdk < 23 {
+                        self.device.disable_mode = false;
+                    }
+                }
+                debug!("Config change: {:?}", self);
+                Config::save_changes(self, &phone.adb_id);
+                Command::none()
+            }
+            Message::BackgroundListRefresh(toggled) => {
+                self.general.background_list_refresh = toggled;
                 debug!("Config change: {:?}", self);
                 Config::save_changes(self, &phone.adb_id);
                 Command::none()
             }
             Message::DisableMode(toggled) => {
-                if phone.android_sdk >= 23 {
+                if phone.android_sdk >= 23 || self.device.root_mode {
                     self.device.disable_mode = toggled;
+                    if toggled {
+                        self.device.suspend_mode = false;
+                    }
+                    debug!("Config change: {:?}", self);
+                    Config::save_changes(self, &phone.adb_id);
+                }
+                Command::none()
+            }
+            Message::SuspendMode(toggled) => {
+                if phone.android_sdk >= 24 {
+                    self.device.suspend_mode = toggled;
+                    if toggled {
+                        self.device.disable_mode = false;
+                    }
                     debug!("Config change: {:?}", self);
                     Config::save_changes(self, &phone.adb_id);
                 }
@@ -72,12 +161,73 @@ impl Settings {
                 Config::save_changes(self, &phone.adb_id);
                 Command::none()
             }
+            Message::TrialMode(toggled) => {
+                self.device.trial_mode = toggled;
+                debug!("Config change: {:?}", self);
+                Config::save_changes(self, &phone.adb_id);
+                Command::none()
+            }
+            Message::RootMode(toggled) => {
+                if !self.general.expert_mode {
+                    return Command::none();
+                }
+                if toggled {
+                    return Command::perform(detect_root(), Message::RootDetected);
+                }
+                self.device.root_mode = false;
+                // Disabling packages needs root before Android 6.0
+                if phone.android_sdk < 23 {
+                    self.device.disable_mode = false;
+                }
+                debug!("Config change: {:?}", self);
+                Config::save_changes(self, &phone.adb_id);
+                Command::none()
+            }
+            Message::RootDetected(rooted) => {
+                info!("[ROOT] su available: {}", rooted);
+                self.root_denied = !rooted;
+                self.device.root_mode = rooted;
+                debug!("Config change: {:?}", self);
+                Config::save_changes(self, &phone.adb_id);
+                Command::none()
+            }
             Message::ApplyTheme(theme) => {
                 self.general.theme = theme.to_string();
                 debug!("Config change: {:?}", self);
                 Config::save_changes(self, &phone.adb_id);
                 Command::none()
             }
+            Message::LanguageSelected(language) => {
+                // Package descriptions are updated the next time the packages are loaded
+                set_language(language.code);
+                self.general.language = language.code.to_string();
+                debug!("Config change: {:?}", self);
+                Config::save_changes(self, &phone.adb_id);
+                Command::none()
+            }
+            Message::ListSourceNameChanged(name) => {
+                self.new_list_source.name = name;
+                Command::none()
+            }
+            Message::ListSourceLocationChanged(location) => {
+                self.new_list_source.location = location;
+                Command::none()
+            }
+            Message::AddListSource => {
+                let source = std::mem::take(&mut self.new_list_source);
+                if !source.name.trim().is_empty() && !source.location.trim().is_empty() {
+                    self.general.list_sources.push(source);
+                    debug!("Config change: {:?}", self);
+                    Config::save_changes(self, &phone.adb_id);
+                }
+                Command::none()
+            }
+            Message::RemoveListSource(i) => {
+                self.general.list_sources.remove(i);
+                debug!("Config change: {:?}", self);
+                Config::save_changes(self, &phone.adb_id);
+                Command::none()
+            }
             Message::UrlPressed(url) => {
                 open_url(url);
                 Command::none()
@@ -91,11 +241,12 @@ impl Settings {
                 {
                     Some(device) => {
                         self.device = device.clone();
+                        self.device.root_mode &= self.general.expert_mode;
                         self.device.backup = BackupSettings {
                             backups: backups.clone(),
                             selected: backups.first().cloned(),
                             users: phone.user_list.clone(),
-                            selected_user: phone.user_list.first().copied(),
+                            selected_user: phone.user_list.first().cloned(),
                             backup_state: String::new(),
                         };
                     }
@@ -104,11 +255,14 @@ impl Settings {
                             device_id: phone.adb_id.clone(),
                             multi_user_mode: phone.android_sdk > 21,
                             disable_mode: false,
+                            suspend_mode: false,
+                            trial_mode: false,
+                            root_mode: false,
                             backup: BackupSettings {
                                 backups: backups.clone(),
                                 selected: backups.first().cloned(),
                                 users: phone.user_list.clone(),
-                                selected_user: phone.user_list.first().copied(),
+                                selected_user: phone.user_list.first().cloned(),
                                 backup_state: String::new(),
                             },
                         }
@@ -138,38 +292,31 @@ impl Settings {
             }
             Message::RestoreDevice => match restore_backup(phone, packages, &self.device) {
                 Ok(r_packages) => {
-                    let mut commands = vec![];
-                    *nb_running_async_adb_commands = 0;
-                    for p in &r_packages {
+                    queue.push(r_packages.iter().map(|p| {
                         let p_info = PackageInfo {
                             i_user: 0,
                             index: p.index,
                             removal: "RESTORE".to_string(),
                         };
-                        for command in p.commands.clone() {
-                            *nb_running_async_adb_commands += 1;
-                            commands.push(Command::perform(
-                                perform_adb_commands(
-                                    command,
-                                    CommandType::PackageManager(p_info.clone()),
-                                ),
-                                Message::RestoringDevice,
-                            ));
-                        }
-                    }
+                        Job::new(
+                            JobKind::Restore,
+                            p.commands.clone(),
+                            CommandType::PackageManager(p_info),
+                        )
+                    }));
                     if r_packages.is_empty() {
                         if get_android_sdk() == 0 {
-                            self.device.backup.backup_state = "Device is not connected".to_string();
+                            self.device.backup.backup_state = tr("Device is not connected");
                         } else {
                             self.device.backup.backup_state =
-                                "Device state is already restored".to_string();
+                                tr("Device state is already restored");
                         }
                     }
                     info!(
                         "[RESTORE] Restoring backup {}",
                         self.device.backup.selected.as_ref().unwrap()
                     );
-                    Command::batch(commands)
+                    Command::none()
                 }
                 Err(e) => {
                     self.device.backup.backup_state = e.to_string();
@@ -179,9 +326,71 @@ impl Settings {
             },
             // Trigger an action in mod.rs (Message::SettingsAction(msg))
             Message::RestoringDevice(_) => Command::none(),
+            Message::NewUserNameChanged(name) => {
+                self.new_user.name = name;
+                Command::none()
+            }
+            Message::NewUserKindSelected(kind) => {
+                self.new_user.kind = kind;
+                Command::none()
+            }
+            Message::CopyStatesToggled(toggled) => {
+                self.new_user.copy_states = toggled;
+                Command::none()
+            }
+            Message::StatesSourceSelected(source) => {
+                self.new_user.states_source = Some(source);
+                Command::none()
+            }
+            Message::CreateUser => {
+                if self.new_user.name.trim().is_empty() {
+                    self.users_status = tr("Enter the name of the new user");
+                    return Command::none();
+                }
+                self.users_status = tr("Creating the user...");
+                Command::perform(
+                    create_user(self.new_user.name.clone(), self.new_user.kind),
+                    Message::UserCreated,
+                )
+            }
+            // The device is reloaded in mod.rs (Message::SettingsAction(msg))
+            Message::UserCreated(res) => {
+                self.users_status = match res {
+                    Ok(id) => {
+                        self.new_user.name.clear();
+                        tr("User {} created").replacen("{}", &id.to_string(), 1)
+                    }
+                    Err(err) => err.to_string(),
+                };
+                Command::none()
+            }
+            Message::UserActionPressed(id, action) => {
+                // Removing a user deletes all its data
+                if action == UserAction::Remove && self.remove_confirmation != Some(id) {
+                    self.remove_confirmation = Some(id);
+                    return Command::none();
+                }
+                self.remove_confirmation = None;
+                Command::perform(manage_user(id, action), Message::UserActionDone)
+            }
+            Message::UserActionDone(res) => {
+                self.users_status = res.map_or_else(|err| err.to_string(), |()| String::new());
+                Command::none()
+            }
         }
     }
 
+    /// Package states given to a user created from the "Users" section
+    pub fn new_user_states_source(&self, phone: &Phone) -> Option<StatesSource> {
+        if !self.new_user.copy_states {
+            return None;
+        }
+        self.new_user
+            .states_source
+            .clone()
+            .or_else(|| phone.user_list.first().cloned().map(StatesSource::User))
+    }
+
     pub fn view(&self, phone: &Phone) -> Element<Message, Renderer<Theme>> {
         let radio_btn_theme = Theme::ALL
             .iter()
@@ -203,27 +412,110 @@ impl Settings {
             .style(style::Container::Frame);
 
         let expert_mode_checkbox = checkbox(
-            "Allow to uninstall packages marked as \"unsafe\" (I KNOW WHAT I AM DOING)",
+            tr("Allow to uninstall packages marked as \"unsafe\" (I KNOW WHAT I AM DOING)"),
             self.general.expert_mode,
             Message::ExpertMode,
         )
         .style(style::CheckBox::SettingsEnabled);
 
-        let expert_mode_descr =
-            text("Most of unsafe packages are known to bootloop the device if removed.")
-                .style(style::Text::Commentary)
-                .size(15);
+        let expert_mode_descr = text(tr(
+            "Most of unsafe packages are known to bootloop the device if removed.",
+        ))
+        .style(style::Text::Commentary)
+        .size(15);
 
-        let general_ctn = container(column![expert_mode_checkbox, expert_mode_descr].spacing(10))
-            .padding(10)
-            .width(Length::Fill)
-            .height(Length::Shrink)
-            .style(style::Container::Frame);
+        let background_refresh_checkbox = checkbox(
+            tr("Start with the cached debloat list and check for updates in the background"),
+            self.general.background_list_refresh,
+            Message::BackgroundListRefresh,
+        )
+        .style(style::CheckBox::SettingsEnabled);
+
+        let language_picklist = pick_list(
+            &Language::ALL[..],
+            Some(current_language()),
+            Message::LanguageSelected,
+        );
+        let language_row = row![text(tr("Language")), language_picklist]
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+        let general_ctn = container(
+            column![
+                language_row,
+                expert_mode_checkbox,
+                expert_mode_descr,
+                background_refresh_checkbox
+            ]
+            .spacing(10),
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .height(Length::Shrink)
+        .style(style::Container::Frame);
+
+        let list_sources = self.general.list_sources.iter().enumerate().fold(
+            column![].spacing(6),
+            |col, (i, source)| {
+                col.push(
+                    row![
+                        text(&source.name).width(150),
+                        text(&source.location).style(style::Text::Commentary),
+                        Space::new(Length::Fill, Length::Shrink),
+                        button(text(tr("Remove")).size(13))
+                            .padding(5)
+                            .on_press(Message::RemoveListSource(i))
+                            .style(style::Button::UninstallPackage),
+                    ]
+                    .spacing(10)
+                    .align_items(Alignment::Center),
+                )
+            },
+        );
+
+        let add_list_source_row = row![
+            text_input(
+                &tr("Name"),
+                &self.new_list_source.name,
+                Message::ListSourceNameChanged
+            )
+            .padding(5)
+            .width(150),
+            text_input(
+                &tr("URL or path of a JSON list"),
+                &self.new_list_source.location,
+                Message::ListSourceLocationChanged
+            )
+            .padding(5),
+            button(text(tr("Add")).size(13))
+                .padding(5)
+                .on_press(Message::AddListSource)
+                .style(style::Button::Primary),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let list_sources_descr = text(tr(
+            "Sources are layered on top of the official list in this order: the last one wins. \
+            Their entries only need an `id` and the fields they override (`list`, `removal`, \
+            `description`). Changes are applied at the next list update.",
+        ))
+        .style(style::Text::Commentary)
+        .size(15);
+
+        let list_sources_ctn =
+            container(column![list_sources_descr, list_sources, add_list_source_row].spacing(10))
+                .padding(10)
+                .width(Length::Fill)
+                .height(Length::Shrink)
+                .style(style::Container::Frame);
 
         let warning_ctn = container(
             row![
-                text("The following settings only affect the currently selected device :")
-                    .style(style::Text::Danger),
+                text(tr(
+                    "The following settings only affect the currently selected device :"
+                ))
+                .style(style::Text::Danger),
                 text(phone.model.clone()),
                 Space::new(Length::Fill, Length::Shrink),
                 text(phone.adb_id.clone()).style(style::Text::Commentary)
@@ -235,9 +527,11 @@ impl Settings {
         .style(style::Container::BorderedFrame);
 
         let multi_user_mode_descr = row![
-            text("This will not affect the following protected work profile users: ")
-                .size(15)
-                .style(style::Text::Commentary),
+            text(tr(
+                "This will not affect the following protected work profile users: "
+            ))
+            .size(15)
+            .style(style::Text::Commentary),
             text(
                 phone
                     .user_list
@@ -252,24 +546,25 @@ impl Settings {
         ];
 
         let multi_user_mode_checkbox = checkbox(
-            "Affect all the users of the device (not only the selected user)",
+            tr("Affect all the users of the device (not only the selected user)"),
             self.device.multi_user_mode,
             Message::MultiUserMode,
         )
         .style(style::CheckBox::SettingsEnabled);
 
-        let disable_checkbox_style = if phone.android_sdk >= 23 {
+        let disable_checkbox_style = if phone.android_sdk >= 23 || self.device.root_mode {
             style::CheckBox::SettingsEnabled
         } else {
             style::CheckBox::SettingsDisabled
         };
 
-        let disable_mode_descr =
-            text("In some cases, it can be better to disable a package instead of uninstalling it")
-                .style(style::Text::Commentary)
-                .size(15);
+        let disable_mode_descr = text(tr(
+            "In some cases, it can be better to disable a package instead of uninstalling it",
+        ))
+        .style(style::Text::Commentary)
+        .size(15);
 
-        let unavailable_btn = button(text("Unavailable").size(13))
+        let unavailable_btn = button(text(tr("Unavailable")).size(13))
             .on_press(Message::UrlPressed(PathBuf::from(
                 "https://github.com/0x192/universal-android-debloater/wiki/FAQ#\
                     why-is-the-disable-mode-setting-not-available-for-my-device",
@@ -280,13 +575,13 @@ impl Settings {
         // Disabling package without root isn't really possible before Android Oreo (8.0)
         // see https://github.com/0x192/universal-android-debloater/wiki/ADB-reference
         let disable_mode_checkbox = checkbox(
-            "Clear and disable packages instead of uninstalling them",
+            tr("Clear and disable packages instead of uninstalling them"),
             self.device.disable_mode,
             Message::DisableMode,
         )
         .style(disable_checkbox_style);
 
-        let disable_setting_row = if phone.android_sdk >= 23 {
+        let disable_setting_row = if phone.android_sdk >= 23 || self.device.root_mode {
             row![
                 disable_mode_checkbox,
                 Space::new(Length::Fill, Length::Shrink),
@@ -301,12 +596,75 @@ impl Settings {
             .width(Length::Fill)
         };
 
+        // `pm suspend` is only available since Android Nougat (7.0)
+        let suspend_mode_checkbox = checkbox(
+            tr("Suspend packages instead of uninstalling them"),
+            self.device.suspend_mode,
+            Message::SuspendMode,
+        )
+        .style(if phone.android_sdk >= 24 {
+            style::CheckBox::SettingsEnabled
+        } else {
+            style::CheckBox::SettingsDisabled
+        });
+
+        let suspend_mode_descr = text(tr(
+            "Suspended packages are greyed out and can't run but they keep their data. \
+            This is the easiest action to revert",
+        ))
+        .style(style::Text::Commentary)
+        .size(15);
+
+        let trial_mode_checkbox = checkbox(
+            tr("Trial mode: reboot the device after each batch and check that it boots"),
+            self.device.trial_mode,
+            Message::TrialMode,
+        )
+        .style(style::CheckBox::SettingsEnabled);
+
+        let trial_mode_descr = text(tr(
+            "A script restoring the packages of the batch is saved before rebooting",
+        ))
+        .style(style::Text::Commentary)
+        .size(15);
+
+        let root_mode_checkbox = checkbox(
+            tr("Root mode: run package changes with su (expert mode)"),
+            self.device.root_mode,
+            Message::RootMode,
+        )
+        .style(if self.general.expert_mode {
+            style::CheckBox::SettingsEnabled
+        } else {
+            style::CheckBox::SettingsDisabled
+        });
+
+        let root_mode_descr = if self.root_denied {
+            text(tr(
+                "su was not found or was denied: grant root to the shell in your root manager",
+            ))
+            .style(style::Text::Danger)
+        } else {
+            text(tr(
+                "Really disables packages (even before Android 6.0), clears the data of \
+                protected packages and removes system apps with a Magisk module",
+            ))
+            .style(style::Text::Commentary)
+        }
+        .size(15);
+
         let device_specific_ctn = container(
             column![
                 multi_user_mode_checkbox,
                 multi_user_mode_descr,
                 disable_setting_row,
                 disable_mode_descr,
+                suspend_mode_checkbox,
+                suspend_mode_descr,
+                trial_mode_checkbox,
+                trial_mode_descr,
+                root_mode_checkbox,
+                root_mode_descr,
             ]
             .spacing(10),
         )
@@ -322,31 +680,32 @@ impl Settings {
         )
         .padding(6);
 
-        let backup_btn = button(text("Backup").horizontal_alignment(alignment::Horizontal::Center))
-            .padding(5)
-            .on_press(Message::BackupDevice)
-            .style(style::Button::Primary)
-            .width(77);
+        let backup_btn =
+            button(text(tr("Backup")).horizontal_alignment(alignment::Horizontal::Center))
+                .padding(5)
+                .on_press(Message::BackupDevice)
+                .style(style::Button::Primary)
+                .width(77);
 
         let restore_btn = |enabled| {
             if enabled {
-                button(text("Restore").horizontal_alignment(alignment::Horizontal::Center))
+                button(text(tr("Restore")).horizontal_alignment(alignment::Horizontal::Center))
                     .padding(5)
                     .on_press(Message::RestoreDevice)
                     .width(77)
             } else {
-                button(text("No backup").horizontal_alignment(alignment::Horizontal::Center))
+                button(text(tr("No backup")).horizontal_alignment(alignment::Horizontal::Center))
                     .padding(5)
                     .width(77)
             }
         };
 
         let locate_backup_btn = if self.device.backup.backups.is_empty() {
-            button("Open backup directory")
+            button(text(tr("Open backup directory")))
                 .padding(5)
                 .style(style::Button::Primary)
         } else {
-            button("Open backup directory")
+            button(text(tr("Open backup directory")))
                 .on_press(Message::UrlPressed(BACKUP_DIR.join(phone.adb_id.clone())))
                 .padding(5)
                 .style(style::Button::Primary)
@@ -354,7 +713,7 @@ impl Settings {
 
         let backup_row = row![
             backup_btn,
-            "Backup the current state of the phone",
+            text(tr("Backup the current state of the phone")),
             Space::new(Length::Fill, Length::Shrink),
             locate_backup_btn,
         ]
@@ -362,13 +721,16 @@ impl Settings {
         .align_items(Alignment::Center);
 
         let restore_row = if self.device.backup.backups.is_empty() {
-            row![restore_btn(false), "Restore the state of the device",]
-                .spacing(10)
-                .align_items(Alignment::Center)
+            row![
+                restore_btn(false),
+                text(tr("Restore the state of the device")),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center)
         } else {
             row![
                 restore_btn(true),
-                "Restore the state of the device",
+                text(tr("Restore the state of the device")),
                 Space::new(Length::Fill, Length::Shrink),
                 text(self.device.backup.backup_state.clone()).style(style::Text::Danger),
                 backup_pick_list,
@@ -383,8 +745,10 @@ impl Settings {
             .height(Length::Shrink)
             .style(style::Container::Frame);
 
+        let users_ctn = self.users_view(phone);
+
         let no_device_ctn = || {
-            container(text("No device detected").style(style::Text::Danger))
+            container(text(tr("No device detected")).style(style::Text::Danger))
                 .padding(10)
                 .width(Length::Fill)
                 .style(style::Container::BorderedFrame)
@@ -392,27 +756,33 @@ impl Settings {
 
         let content = if phone.adb_id.clone().is_empty() {
             column![
-                text("Theme").size(25),
+                text(tr("Theme")).size(25),
                 theme_ctn,
-                text("General").size(25),
+                text(tr("General")).size(25),
                 general_ctn,
-                text("Current device").size(25),
+                text(tr("List sources")).size(25),
+                list_sources_ctn,
+                text(tr("Current device")).size(25),
                 no_device_ctn(),
-                text("Backup / Restore").size(25),
+                text(tr("Backup / Restore")).size(25),
                 no_device_ctn(),
             ]
             .width(Length::Fill)
             .spacing(20)
         } else {
             column![
-                text("Theme").size(25),
+                text(tr("Theme")).size(25),
                 theme_ctn,
-                text("General").size(25),
+                text(tr("General")).size(25),
                 general_ctn,
-                text("Current device").size(25),
+                text(tr("List sources")).size(25),
+                list_sources_ctn,
+                text(tr("Current device")).size(25),
                 warning_ctn,
                 device_specific_ctn,
                 backup_restore_ctn,
+                text(tr("Users")).size(25),
+                users_ctn,
             ]
             .width(Length::Fill)
             .spacing(20)
@@ -424,4 +794,124 @@ impl Settings {
             .height(Length::Fill)
             .into()
     }
+
+    fn users_view(&self, phone: &Phone) -> Element<Message, Renderer<Theme>> {
+        let action_btn = |label: String, id, action| {
+            button(text(label).size(13))
+                .padding(5)
+                .on_press(Message::UserActionPressed(id, action))
+                .style(style::Button::Primary)
+        };
+
+        let users = phone
+            .user_list
+            .iter()
+            .fold(column![].spacing(6), |col, user| {
+                let state = if user.running {
+                    text(tr("Running")).style(style::Text::Ok)
+                } else {
+                    text(tr("Stopped")).style(style::Text::Commentary)
+                };
+                let mut user_row =
+                    row![text(user.to_string()).width(Length::Fill), state.width(80)]
+                        .spacing(10)
+                        .align_items(Alignment::Center);
+                // The owner can't be stopped or removed
+                if user.id != 0 {
+                    user_row = user_row.push(if user.running {
+                        action_btn(tr("Stop"), user.id, UserAction::Stop)
+                    } else {
+                        action_btn(tr("Start"), user.id, UserAction::Start)
+                    });
+                }
+                user_row = user_row.push(action_btn(tr("Switch to"), user.id, UserAction::Switch));
+                if user.id != 0 {
+                    let label = if self.remove_confirmation == Some(user.id) {
+                        tr("Confirm removal")
+                    } else {
+                        tr("Remove")
+                    };
+                    user_row = user_row.push(
+                        button(text(label).size(13))
+                            .padding(5)
+                            .on_press(Message::UserActionPressed(user.id, UserAction::Remove))
+                            .style(style::Button::UninstallPackage),
+                    );
+                }
+                col.push(user_row)
+            });
+
+        let states_sources: Vec<StatesSource> = phone
+            .user_list
+            .iter()
+            .filter(|u| !u.protected)
+            .cloned()
+            .map(StatesSource::User)
+            .chain(
+                self.device
+                    .backup
+                    .backups
+                    .iter()
+                    .cloned()
+                    .map(StatesSource::Backup),
+            )
+            .chain([StatesSource::Recommendations])
+            .collect();
+        let states_source = self
+            .new_user
+            .states_source
+            .clone()
+            .or_else(|| phone.user_list.first().cloned().map(StatesSource::User));
+
+        let create_user_row = row![
+            text_input(
+                &tr("Name"),
+                &self.new_user.name,
+                Message::NewUserNameChanged
+            )
+            .padding(5)
+            .width(150),
+            pick_list(
+                &NewUserKind::ALL[..],
+                Some(self.new_user.kind),
+                Message::NewUserKindSelected
+            ),
+            checkbox(
+                tr("Then apply the package states of"),
+                self.new_user.copy_states,
+                Message::CopyStatesToggled
+            )
+            .style(style::CheckBox::SettingsEnabled),
+            pick_list(states_sources, states_source, Message::StatesSourceSelected),
+            Space::new(Length::Fill, Length::Shrink),
+            button(text(tr("Create")).size(13))
+                .padding(5)
+                .on_press(Message::CreateUser)
+                .style(style::Button::Primary),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        let users_descr = text(tr(
+            "A disposable user is removed by Android as soon as it is stopped or \
+            the device switches to another user.",
+        ))
+        .style(style::Text::Commentary)
+        .size(15);
+
+        container(
+            column![
+                users,
+                create_user_row,
+                users_descr,
+                text(&self.users_status).style(style::Text::Danger),
+            ]
+            .spacing(10),
+        )
+        .padding(10)
+        .width(Length::Fill)
+        .height(Length::Shrink)
+        .style(style::Container::Frame)
+        .into()
+    }
 }
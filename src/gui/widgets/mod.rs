@@ -1,3 +1,4 @@
 pub mod modal;
 pub mod navigation_menu;
 pub mod package_row;
+pub mod queue_bar;
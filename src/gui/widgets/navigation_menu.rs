@@ -1,3 +1,4 @@
+use crate::core::i18n::tr;
 pub use crate::core::sync::Phone;
 use crate::core::theme::Theme;
 use crate::core::update::{SelfUpdateState, SelfUpdateStatus};
@@ -29,7 +30,7 @@ pub fn nav_menu<'a>(
     .padding(5)
     .style(style::Button::Refresh);
 
-    let reboot_btn = button("Reboot")
+    let reboot_btn = button(text(tr("Reboot")))
         .on_press(Message::RebootButtonPressed)
         .padding(5)
         .style(style::Button::Refresh);
@@ -37,10 +38,11 @@ pub fn nav_menu<'a>(
     #[allow(clippy::option_if_let_else)]
     let uad_version_text = if let Some(r) = &self_update_state.latest_release {
         if self_update_state.status == SelfUpdateStatus::Updating {
-            Text::new("Updating please wait...")
+            Text::new(tr("Updating please wait..."))
         } else {
             Text::new(format!(
-                "New UAD version available {} -> {}",
+                "{} {} -> {}",
+                tr("New UAD version available"),
                 env!("CARGO_PKG_VERSION"),
                 r.tag_name
             ))
@@ -50,30 +52,35 @@ pub fn nav_menu<'a>(
     };
 
     let apps_btn = if self_update_state.latest_release.is_some() {
-        button("Update")
+        button(text(tr("Update")))
             .on_press(Message::AboutAction(AboutMessage::DoSelfUpdate))
             .padding(5)
             .style(style::Button::SelfUpdate)
     } else {
-        button("Apps")
+        button(text(tr("Apps")))
             .on_press(Message::AppsPress)
             .padding(5)
             .style(style::Button::Primary)
     };
 
-    let about_btn = button("About")
+    let about_btn = button(text(tr("About")))
         .on_press(Message::AboutPressed)
         .padding(5)
         .style(style::Button::Primary);
 
-    let settings_btn = button("Settings")
+    let compare_btn = button(text(tr("Compare")))
+        .on_press(Message::ComparePressed)
+        .padding(5)
+        .style(style::Button::Primary);
+
+    let settings_btn = button(text(tr("Settings")))
         .on_press(Message::SettingsPressed)
         .padding(5)
         .style(style::Button::Primary);
 
     let device_list_text = match apps_view.loading_state {
-        ListLoadingState::FindingPhones(_) => text("finding connected phone..."),
-        _ => text("no devices/emulators found"),
+        ListLoadingState::FindingPhones(_) => text(tr("finding connected phone...")),
+        _ => text(tr("no devices/emulators found")),
     };
 
     let row = match selected_device {
@@ -84,6 +91,7 @@ pub fn nav_menu<'a>(
             Space::new(Length::Fill, Length::Shrink),
             uad_version_text,
             apps_btn,
+            compare_btn,
             about_btn,
             settings_btn,
         ]
@@ -97,6 +105,7 @@ pub fn nav_menu<'a>(
             Space::new(Length::Fill, Length::Shrink),
             uad_version_text,
             apps_btn,
+            compare_btn,
             about_btn,
             settings_btn,
         ]
@@ -1,19 +1,28 @@
-use crate::core::sync::Phone;
+use crate::core::i18n::tr;
+use crate::core::sync::{AdbError, Phone};
 use crate::core::theme::Theme;
-use crate::core::uad_lists::{PackageState, Removal, UadList};
+use crate::core::uad_lists::{NetworkState, PackageState, Removal, UadList};
 use crate::gui::style;
 use crate::gui::views::settings::Settings;
 
-use iced::widget::{button, checkbox, row, text, Space};
+use iced::widget::{button, checkbox, row, text, tooltip, Space};
 use iced::{alignment, Alignment, Command, Element, Length, Renderer};
 
 #[derive(Clone, Debug)]
 pub struct PackageRow {
     pub name: String,
     pub state: PackageState,
+    pub network: NetworkState,
     pub description: String,
+    pub apk_name: String,  // name of the APK (or of its directory)
+    pub code_path: String, // path of the APK (or of its directory) on the device
     pub uad_list: UadList,
     pub removal: Removal,
+    pub disabled_components: Vec<String>,
+    pub update_path: Option<String>, // path of the update of an updated system app
+    pub source: Option<String>,      // list source overriding the official list
+    pub boot_critical: bool,         // removal requires a typed confirmation
+    pub error: Option<AdbError>,     // failure of the last action on the package
     pub selected: bool,
     pub current: bool,
 }
@@ -22,13 +31,17 @@ pub struct PackageRow {
 pub enum Message {
     PackagePressed,
     ActionPressed,
+    NetworkPressed,
     ToggleSelection(bool),
+    RetryPressed,
 }
 
 impl PackageRow {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         name: &str,
         state: PackageState,
+        network: NetworkState,
         description: &str,
         uad_list: UadList,
         removal: Removal,
@@ -38,9 +51,17 @@ impl PackageRow {
         Self {
             name: name.to_string(),
             state,
+            network,
             description: description.to_string(),
+            apk_name: String::new(),
+            code_path: String::new(),
             uad_list,
             removal,
+            disabled_components: vec![],
+            update_path: None,
+            source: None,
+            boot_critical: false,
+            error: None,
             selected,
             current,
         }
@@ -50,7 +71,7 @@ impl PackageRow {
         Command::none()
     }
 
-    pub fn view(&self, settings: &Settings, _phone: &Phone) -> Element<Message, Renderer<Theme>> {
+    pub fn view(&self, settings: &Settings, phone: &Phone) -> Element<Message, Renderer<Theme>> {
         //let trash_svg = format!("{}/resources/assets/trash.svg", env!("CARGO_MANIFEST_DIR"));
         //let restore_svg = format!("{}/resources/assets/rotate.svg", env!("CARGO_MANIFEST_DIR"));
         let button_style;
@@ -60,23 +81,15 @@ impl PackageRow {
 
         match self.state {
             PackageState::Enabled => {
-                action_text = if settings.device.disable_mode {
-                    "Disable"
-                } else {
-                    "Uninstall"
-                };
+                action_text = tr(action_text_for(self.state, settings.device.removal_state()));
                 button_style = style::Button::UninstallPackage;
             }
-            PackageState::Disabled => {
-                action_text = "Enable";
-                button_style = style::Button::RestorePackage;
-            }
-            PackageState::Uninstalled => {
-                action_text = "Restore";
+            PackageState::Disabled | PackageState::Uninstalled | PackageState::Suspended => {
+                action_text = tr(action_text_for(self.state, settings.device.removal_state()));
                 button_style = style::Button::RestorePackage;
             }
             PackageState::All => {
-                action_text = "Error";
+                action_text = tr("Error");
                 button_style = style::Button::RestorePackage;
                 warn!("Incredible! Something impossible happened!");
             }
@@ -106,13 +119,82 @@ impl PackageRow {
             );
         }
 
+        // Before Android 13, only background data can be restricted
+        let full_network = phone.android_sdk >= 33;
+        let network_label = match (self.network == NetworkState::Blocked, full_network) {
+            (true, true) => tr("Allow network"),
+            (false, true) => tr("Block network"),
+            (true, false) => tr("Allow background data"),
+            (false, false) => tr("Block background data"),
+        };
+        // Network restrictions need `cmd netpolicy` and `pm list packages -U` (Android 8.0+)
+        let network_btn = if phone.android_sdk >= 26 && self.state != PackageState::Uninstalled {
+            button(
+                text(network_label)
+                    .horizontal_alignment(alignment::Horizontal::Center)
+                    .width(160),
+            )
+            .on_press(Message::NetworkPressed)
+        } else {
+            button(
+                text(network_label)
+                    .horizontal_alignment(alignment::Horizontal::Center)
+                    .width(160),
+            )
+        };
+
+        let network_text = match (self.network == NetworkState::Blocked, full_network) {
+            (true, true) => text(tr("Network blocked")).style(style::Text::Danger),
+            (true, false) => text(tr("Background data blocked")).style(style::Text::Danger),
+            (false, _) => text(""),
+        };
+
+        let update_text = if self.update_path.is_some() {
+            text(tr("updated")).style(style::Text::Commentary)
+        } else {
+            text("")
+        };
+
+        let source_text = self.source.as_ref().map_or_else(
+            || text(""),
+            |source| text(format!("[{source}]")).style(style::Text::Commentary),
+        );
+
+        let error_row = self.error.as_ref().map_or_else(
+            || row![],
+            |error| {
+                row![
+                    tooltip(
+                        text(tr("failed")).style(style::Text::Danger),
+                        error.to_string(),
+                        tooltip::Position::Top,
+                    )
+                    .gap(10)
+                    .padding(10)
+                    .size(15)
+                    .style(style::Container::Tooltip),
+                    button(text(tr("Retry")))
+                        .on_press(Message::RetryPressed)
+                        .style(style::Button::Primary),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center)
+            },
+        );
+
         row![
             button(
                 row![
                     selection_checkbox,
                     text(&self.name).width(Length::FillPortion(8)),
+                    source_text,
+                    update_text,
+                    network_text,
+                    error_row,
+                    network_btn.style(style::Button::Primary),
                     action_btn.style(button_style)
                 ]
+                .spacing(10)
                 .align_items(Alignment::Center)
             )
             .padding(8)
@@ -129,3 +211,18 @@ impl PackageRow {
         .into()
     }
 }
+
+/// Text of the action performed on a package in `state` (to translate with `tr`)
+pub const fn action_text_for(state: PackageState, removal_state: PackageState) -> &'static str {
+    match state {
+        PackageState::Enabled => match removal_state {
+            PackageState::Disabled => "Disable",
+            PackageState::Suspended => "Suspend",
+            _ => "Uninstall",
+        },
+        PackageState::Disabled => "Enable",
+        PackageState::Uninstalled => "Restore",
+        PackageState::Suspended => "Unsuspend",
+        PackageState::All => "Impossible",
+    }
+}
@@ -0,0 +1,54 @@
+use crate::core::i18n::tr;
+use crate::core::queue::Queue;
+use crate::core::theme::Theme;
+use crate::gui::{style, Message};
+use iced::widget::{button, container, progress_bar, row, text};
+use iced::{Alignment, Element, Length, Renderer};
+
+/// Progress of the queued adb commands, or the summary of the last run once they are done
+pub fn queue_bar(queue: &Queue) -> Element<Message, Renderer<Theme>> {
+    let (done, total) = queue.progress();
+
+    let content = if queue.is_idle() {
+        let summary = tr("Done: {} succeeded, {} failed, {} cancelled")
+            .replacen("{}", &queue.summary.succeeded.to_string(), 1)
+            .replacen("{}", &queue.summary.failed.to_string(), 1)
+            .replacen("{}", &queue.summary.cancelled.to_string(), 1);
+        let close_btn = button(text(tr("Close")))
+            .on_press(Message::QueueClear)
+            .padding(5)
+            .style(style::Button::Primary);
+        row![text(summary).width(Length::Fill), close_btn]
+    } else {
+        let pause_btn = if queue.paused {
+            button(text(tr("Resume"))).on_press(Message::QueuePaused(false))
+        } else {
+            button(text(tr("Pause"))).on_press(Message::QueuePaused(true))
+        }
+        .padding(5)
+        .style(style::Button::Primary);
+        let cancel_btn = button(text(tr("Cancel")))
+            .on_press(Message::QueueCancel)
+            .padding(5)
+            .style(style::Button::UninstallPackage);
+        row![
+            progress_bar(0.0..=total as f32, done as f32)
+                .height(Length::Fixed(10.0))
+                .style(style::ProgressBar::Default),
+            text(format!("{done} / {total}")),
+            pause_btn,
+            cancel_btn,
+        ]
+    };
+
+    container(
+        content
+            .width(Length::Fill)
+            .align_items(Alignment::Center)
+            .spacing(10),
+    )
+    .width(Length::Fill)
+    .padding(10)
+    .style(style::Container::Frame)
+    .into()
+}